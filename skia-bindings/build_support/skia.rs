@@ -12,6 +12,9 @@ mod lib {
     pub const SKIA_BINDINGS: &str = "skia-bindings";
     pub const SKSHAPER: &str = "skshaper";
     pub const SKPARAGRAPH: &str = "skparagraph";
+    pub const SKOTTIE: &str = "skottie";
+    pub const SKSG: &str = "sksg";
+    pub const PARTICLES: &str = "particles";
 }
 
 /// Feature identifiers define the additional configuration parts of the binaries to download.
@@ -20,6 +23,9 @@ mod feature_id {
     pub const SVG: &str = "svg";
     pub const SHAPER: &str = "shaper";
     pub const TEXTLAYOUT: &str = "textlayout";
+    pub const WEBP: &str = "webp";
+    pub const ANIMATION: &str = "animation";
+    pub const PARTICLES: &str = "particles";
 }
 
 /// The defaults for the Skia build configuration.
@@ -46,6 +52,10 @@ impl Default for BuildConfiguration {
             }
         };
 
+        if cfg!(feature = "animation") && text_layout == TextLayout::None {
+            panic!("invalid feature configuration, feature 'shaper' must be enabled for feature 'animation'")
+        }
+
         BuildConfiguration {
             on_windows: cargo::host().is_windows(),
             // Note that currently, we don't support debug Skia builds,
@@ -56,9 +66,10 @@ impl Default for BuildConfiguration {
                 vulkan: cfg!(feature = "vulkan"),
                 svg: cfg!(feature = "svg"),
                 text_layout,
-                animation: false,
+                webp: cfg!(feature = "webp"),
+                animation: cfg!(feature = "animation"),
                 dng: false,
-                particles: false,
+                particles: cfg!(feature = "particles"),
             },
             all_skia_libs,
             definitions: Vec::new(),
@@ -101,13 +112,17 @@ pub struct Features {
     /// Features related to text layout.
     text_layout: TextLayout,
 
-    /// Build with animation support (yet unsupported, no wrappers).
+    /// Build with WebP decoding and encoding support?
+    webp: bool,
+
+    /// Build the skottie module for Lottie animation playback (requires text layout support for the
+    /// skshaper module).
     animation: bool,
 
     /// Support DNG file format (currently unsupported because of build errors).
     dng: bool,
 
-    /// Build the particles module (unsupported, no wrappers).
+    /// Build the particles module for particle effects.
     particles: bool,
 }
 
@@ -140,6 +155,8 @@ impl TextLayout {
                 ("skia_use_system_icu", no()),
                 ("skia_use_harfbuzz", yes()),
                 ("skia_use_system_harfbuzz", no()),
+                // subsets the fonts embedded in PDF documents, off by default.
+                ("skia_pdf_subset_harfbuzz", yes()),
                 ("skia_use_sfntly", no()),
             ]);
         }
@@ -166,6 +183,17 @@ impl TextLayout {
         }
     }
 
+    /// Include directories relative to `skia/` the binding sources need.
+    fn include_directories(&self) -> Vec<PathBuf> {
+        match self {
+            // paragraph.cpp uses ICU's break iterators.
+            TextLayout::ShaperAndParagraph => {
+                vec!["third_party/externals/icu/source/common".into()]
+            }
+            _ => Vec::new(),
+        }
+    }
+
     fn patches(&self) -> Vec<Patch> {
         match self {
             TextLayout::ShaperAndParagraph => vec![Patch {
@@ -206,6 +234,13 @@ pub struct FinalBuildConfiguration {
 
     /// The binding source files to compile.
     pub binding_sources: Vec<PathBuf>,
+
+    /// Directories relative to `skia/` that contain Skia sources which are not part of a
+    /// library Skia builds, but are compiled into the `skia-bindings` library.
+    pub skia_source_directories: Vec<PathBuf>,
+
+    /// Additional include directories relative to `skia/`.
+    pub skia_include_directories: Vec<PathBuf>,
 }
 
 impl FinalBuildConfiguration {
@@ -224,7 +259,7 @@ impl FinalBuildConfiguration {
                 ),
                 ("skia_use_system_libjpeg_turbo", no()),
                 ("skia_use_system_libpng", no()),
-                ("skia_use_libwebp", no()),
+                ("skia_use_libwebp", if features.webp { yes() } else { no() }),
                 ("skia_use_system_zlib", no()),
                 (
                     "skia_enable_skottie",
@@ -260,6 +295,10 @@ impl FinalBuildConfiguration {
 
             args.extend(features.text_layout.skia_args());
 
+            if features.webp {
+                args.push(("skia_use_system_libwebp", no()));
+            }
+
             if features.vulkan {
                 args.push(("skia_use_vulkan", yes()));
                 args.push(("skia_enable_spirv_validation", no()));
@@ -308,7 +347,7 @@ impl FinalBuildConfiguration {
                 args.push(("skia_use_expat", no()));
             }
 
-            if build.all_skia_libs {
+            if build.all_skia_libs || features.particles {
                 // m78: modules/particles forgets to set SKIA_IMPLEMENTATION=1 and so
                 // expects system vulkan headers.
                 flags.push("-DSKIA_IMPLEMENTATION=1");
@@ -331,6 +370,12 @@ impl FinalBuildConfiguration {
             let mut files = Vec::new();
             files.push("obj/skia.ninja".into());
             files.extend(features.text_layout.ninja_files());
+            if features.animation {
+                files.push("obj/modules/skottie/skottie.ninja".into());
+            }
+            if features.particles {
+                files.push("obj/modules/particles/particles.ninja".into());
+            }
             files
         };
 
@@ -338,15 +383,35 @@ impl FinalBuildConfiguration {
             let mut sources: Vec<PathBuf> = Vec::new();
             sources.push("src/bindings.cpp".into());
             sources.extend(features.text_layout.sources());
+            if features.animation {
+                sources.push("src/skottie.cpp".into());
+            }
+            if features.particles {
+                sources.push("src/particles.cpp".into());
+            }
+            if features.svg {
+                sources.push("src/svg.cpp".into());
+            }
             sources
         };
 
+        let skia_source_directories = {
+            let mut directories: Vec<PathBuf> = Vec::new();
+            // m79: the SVG DOM is built by Skia only if tools are enabled.
+            if features.svg {
+                directories.push("experimental/svg/model".into());
+            }
+            directories
+        };
+
         FinalBuildConfiguration {
             skia_patches: features.text_layout.patches(),
             gn_args,
             ninja_files,
             definitions: build.definitions.clone(),
             binding_sources,
+            skia_source_directories,
+            skia_include_directories: features.text_layout.include_directories(),
         }
     }
 }
@@ -398,6 +463,19 @@ impl BinariesConfiguration {
         if features.svg {
             feature_ids.push(feature_id::SVG);
         }
+        if features.webp {
+            feature_ids.push(feature_id::WEBP);
+        }
+        if features.animation {
+            feature_ids.push(feature_id::ANIMATION);
+            // skottie depends on sksg and skshaper, so it must be linked before them.
+            built_libraries.push(lib::SKOTTIE.into());
+            built_libraries.push(lib::SKSG.into());
+        }
+        if features.particles {
+            feature_ids.push(feature_id::PARTICLES);
+            built_libraries.push(lib::PARTICLES.into());
+        }
         match features.text_layout {
             TextLayout::None => {}
             TextLayout::ShaperOnly => {
@@ -672,6 +750,8 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         .whitelist_type("SkOpBuilder")
         // svg/
         .whitelist_type("SkSVGCanvas")
+        // experimental/svg/model/
+        .whitelist_type("SkSVGDOM")
         // utils/
         .whitelist_function("Sk3LookAt")
         .whitelist_function("Sk3Perspective")
@@ -699,6 +779,14 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         // misc
         .whitelist_var("SK_Color.*")
         .whitelist_var("kAll_GrBackendState")
+        .whitelist_type("RustExecutor")
+        .whitelist_type("RustImageGenerator")
+        .whitelist_type("RustLogger")
+        .whitelist_type("RustPropertyObserver")
+        .whitelist_type("RustResourceProvider")
+        .whitelist_type("RustStream")
+        .whitelist_type("RustStreamAsset")
+        .whitelist_type("RustWStream")
         //
         .use_core()
         .clang_arg("-std=c++14")
@@ -719,13 +807,30 @@ fn bindgen_gen(build: &FinalBuildConfiguration, current_dir: &Path, output_direc
         builder = builder.header(source);
     }
 
+    for directory in &build.skia_source_directories {
+        let directory = current_dir.join("skia").join(directory);
+        let mut sources: Vec<PathBuf> = fs::read_dir(&directory)
+            .unwrap_or_else(|_| panic!("failed to read {}", directory.display()))
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "cpp"))
+            .collect();
+        sources.sort();
+        cc_build.files(sources);
+    }
+
     // TODO: may put the include paths into the FinalBuildConfiguration?
 
     let include_path = current_dir.join("skia");
     cargo::add_dependent_path(include_path.join("include"));
 
     builder = builder.clang_arg(format!("-I{}", include_path.display()));
-    cc_build.include(include_path);
+    cc_build.include(&include_path);
+
+    for directory in &build.skia_include_directories {
+        let directory = include_path.join(directory);
+        builder = builder.clang_arg(format!("-I{}", directory.display()));
+        cc_build.include(directory);
+    }
 
     let definitions = {
         let mut definitions = Vec::new();
@@ -824,6 +929,14 @@ const OPAQUE_TYPES: &[&str] = &[
     "SkDeque",
     "SkDeque_Iter",
     "GrGLInterface_Functions",
+    // SVG DOM internals (node tree and id map).
+    "SkSVGDOM",
+    // skottie (m79), scene graph internals.
+    "skottie::Animation",
+    "skottie::Animation::Builder",
+    // particles (m79), SkSL programs and particle state.
+    "SkParticleEffect",
+    "SkParticleEffectParams",
     // SkShaper (m77) Trivial*Iterator classes create two vtable pointers.
     "SkShaper_TrivialBiDiRunIterator",
     "SkShaper_TrivialFontRunIterator",
@@ -878,6 +991,13 @@ const OPAQUE_TYPES: &[&str] = &[
     "SkShaper_ScriptRunIterator",
     "SkContourMeasure",
     "SkDocument",
+    // codec/
+    "SkCodec",
+    "SkAndroidCodec",
+    // encode/
+    "SkEncoder",
+    // android/
+    "SkAnimatedImage",
 ];
 
 #[derive(Debug)]
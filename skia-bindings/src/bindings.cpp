@@ -1,5 +1,9 @@
 #include "bindings.h"
+// android/
+#include "include/android/SkAnimatedImage.h"
 // codec/
+#include "include/codec/SkAndroidCodec.h"
+#include "include/codec/SkCodec.h"
 #include "include/codec/SkEncodedOrigin.h"
 // core/
 #include "include/core/SkAnnotation.h"
@@ -12,6 +16,7 @@
 #include "include/core/SkDeferredDisplayListRecorder.h"
 #include "include/core/SkDrawLooper.h"
 #include "include/core/SkDrawable.h"
+#include "include/core/SkExecutor.h"
 #include "include/core/SkDocument.h"
 #include "include/core/SkFlattenable.h"
 #include "include/core/SkFont.h"
@@ -52,6 +57,10 @@
 #include "include/core/SkYUVASizeInfo.h"
 // docs/
 #include "include/docs/SkPDFDocument.h"
+// encode/
+#include "include/encode/SkEncoder.h"
+#include "include/encode/SkJpegEncoder.h"
+#include "include/encode/SkPngEncoder.h"
 // effects/
 #include "include/effects/Sk1DPathEffect.h"
 #include "include/effects/Sk2DPathEffect.h"
@@ -119,6 +128,10 @@
 #include "include/svg/SkSVGCanvas.h"
 #endif
 
+#if defined(SK_HAS_WEBP_LIBRARY)
+#include "include/encode/SkWebpEncoder.h"
+#endif
+
 template<typename T>
 inline sk_sp<T> sp(T* pt) {
     return sk_sp<T>(pt);
@@ -132,6 +145,203 @@ extern "C" void C_SkEncodedOriginToMatrix(SkEncodedOrigin origin, int w, int h,
     *matrix = SkEncodedOriginToMatrix(origin, w, h);
 }
 
+//
+// codec/SkCodec.h
+//
+
+extern "C" void C_SkCodec_delete(SkCodec* self) {
+    delete self;
+}
+
+extern "C" SkCodec* C_SkCodec_MakeFromData(SkData* data) {
+    return SkCodec::MakeFromData(sp(data)).release();
+}
+
+// note: this function _consumes_ the stream, even if no codec could be created.
+extern "C" SkCodec* C_SkCodec_MakeFromStream(SkStream* stream, SkCodec::Result* result) {
+    return SkCodec::MakeFromStream(std::unique_ptr<SkStream>(stream), result).release();
+}
+
+extern "C" size_t C_SkCodec_MinBufferedBytesNeeded() {
+    return SkCodec::MinBufferedBytesNeeded();
+}
+
+extern "C" void C_SkCodec_getInfo(const SkCodec* self, SkImageInfo* uninitialized) {
+    new(uninitialized) SkImageInfo(self->getInfo());
+}
+
+extern "C" SkISize C_SkCodec_dimensions(const SkCodec* self) {
+    return self->dimensions();
+}
+
+extern "C" SkEncodedOrigin C_SkCodec_getOrigin(const SkCodec* self) {
+    return self->getOrigin();
+}
+
+extern "C" const uint8_t* C_SkCodec_getICCProfile(const SkCodec* self, size_t* size) {
+    auto profile = self->getICCProfile();
+    if (!profile) {
+        *size = 0;
+        return nullptr;
+    }
+    *size = profile->size;
+    return profile->buffer;
+}
+
+extern "C" SkISize C_SkCodec_getScaledDimensions(const SkCodec* self, float desiredScale) {
+    return self->getScaledDimensions(desiredScale);
+}
+
+extern "C" bool C_SkCodec_getValidSubset(const SkCodec* self, SkIRect* desiredSubset) {
+    return self->getValidSubset(desiredSubset);
+}
+
+extern "C" SkEncodedImageFormat C_SkCodec_getEncodedFormat(const SkCodec* self) {
+    return self->getEncodedFormat();
+}
+
+extern "C" SkCodec::Result C_SkCodec_getPixels(SkCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes, const SkCodec::Options* options) {
+    return self->getPixels(*info, pixels, rowBytes, options);
+}
+
+extern "C" const char* C_SkCodec_ResultToString(SkCodec::Result result) {
+    return SkCodec::ResultToString(result);
+}
+
+extern "C" int C_SkCodec_getFrameCount(SkCodec* self) {
+    return self->getFrameCount();
+}
+
+extern "C" bool C_SkCodec_getFrameInfo(const SkCodec* self, int index, SkCodec::FrameInfo* info) {
+    return self->getFrameInfo(index, info);
+}
+
+extern "C" int C_SkCodec_getRepetitionCount(SkCodec* self) {
+    return self->getRepetitionCount();
+}
+
+extern "C" SkCodec::Result C_SkCodec_startIncrementalDecode(SkCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes, const SkCodec::Options* options) {
+    return self->startIncrementalDecode(*info, pixels, rowBytes, options);
+}
+
+extern "C" SkCodec::Result C_SkCodec_incrementalDecode(SkCodec* self, int* rowsDecoded) {
+    return self->incrementalDecode(rowsDecoded);
+}
+
+extern "C" SkCodec::Result C_SkCodec_startScanlineDecode(SkCodec* self, const SkImageInfo* info, const SkCodec::Options* options) {
+    return self->startScanlineDecode(*info, options);
+}
+
+extern "C" int C_SkCodec_getScanlines(SkCodec* self, void* dst, int countLines, size_t rowBytes) {
+    return self->getScanlines(dst, countLines, rowBytes);
+}
+
+extern "C" bool C_SkCodec_skipScanlines(SkCodec* self, int countLines) {
+    return self->skipScanlines(countLines);
+}
+
+extern "C" SkCodec::SkScanlineOrder C_SkCodec_getScanlineOrder(const SkCodec* self) {
+    return self->getScanlineOrder();
+}
+
+extern "C" int C_SkCodec_nextScanline(const SkCodec* self) {
+    return self->nextScanline();
+}
+
+extern "C" int C_SkCodec_outputScanline(const SkCodec* self, int inputScanline) {
+    return self->outputScanline(inputScanline);
+}
+
+//
+// codec/SkAndroidCodec.h
+//
+
+extern "C" void C_SkAndroidCodec_delete(SkAndroidCodec* self) {
+    delete self;
+}
+
+// note: this function _consumes_ the codec.
+extern "C" SkAndroidCodec* C_SkAndroidCodec_MakeFromCodec(SkCodec* codec) {
+    return SkAndroidCodec::MakeFromCodec(std::unique_ptr<SkCodec>(codec)).release();
+}
+
+extern "C" SkAndroidCodec* C_SkAndroidCodec_MakeFromData(SkData* data) {
+    return SkAndroidCodec::MakeFromData(sp(data)).release();
+}
+
+extern "C" void C_SkAndroidCodec_getInfo(const SkAndroidCodec* self, SkImageInfo* uninitialized) {
+    new(uninitialized) SkImageInfo(self->getInfo());
+}
+
+extern "C" SkEncodedImageFormat C_SkAndroidCodec_getEncodedFormat(const SkAndroidCodec* self) {
+    return self->getEncodedFormat();
+}
+
+extern "C" SkEncodedOrigin C_SkAndroidCodec_getOrigin(const SkAndroidCodec* self) {
+    return self->codec()->getOrigin();
+}
+
+extern "C" SkColorType C_SkAndroidCodec_computeOutputColorType(SkAndroidCodec* self, SkColorType requestedColorType) {
+    return self->computeOutputColorType(requestedColorType);
+}
+
+extern "C" SkAlphaType C_SkAndroidCodec_computeOutputAlphaType(SkAndroidCodec* self, bool requestedUnpremul) {
+    return self->computeOutputAlphaType(requestedUnpremul);
+}
+
+extern "C" int C_SkAndroidCodec_computeSampleSize(const SkAndroidCodec* self, SkISize* size) {
+    return self->computeSampleSize(size);
+}
+
+extern "C" SkISize C_SkAndroidCodec_getSampledDimensions(const SkAndroidCodec* self, int sampleSize) {
+    return self->getSampledDimensions(sampleSize);
+}
+
+extern "C" bool C_SkAndroidCodec_getSupportedSubset(const SkAndroidCodec* self, SkIRect* desiredSubset) {
+    return self->getSupportedSubset(desiredSubset);
+}
+
+extern "C" SkISize C_SkAndroidCodec_getSampledSubsetDimensions(const SkAndroidCodec* self, int sampleSize, const SkIRect* subset) {
+    return self->getSampledSubsetDimensions(sampleSize, *subset);
+}
+
+extern "C" SkCodec::Result C_SkAndroidCodec_getAndroidPixels(SkAndroidCodec* self, const SkImageInfo* info, void* pixels, size_t rowBytes, SkCodec::ZeroInitialized zeroInitialized, SkIRect* subset, int sampleSize) {
+    SkAndroidCodec::AndroidOptions options;
+    options.fZeroInitialized = zeroInitialized;
+    options.fSubset = subset;
+    options.fSampleSize = sampleSize;
+    return self->getAndroidPixels(*info, pixels, rowBytes, &options);
+}
+
+//
+// android/SkAnimatedImage.h
+//
+
+// note: this function _consumes_ / deletes the codec.
+extern "C" SkAnimatedImage* C_SkAnimatedImage_Make(SkCodec* codec) {
+    return SkAnimatedImage::Make(SkAndroidCodec::MakeFromCodec(std::unique_ptr<SkCodec>(codec))).release();
+}
+
+extern "C" void C_SkAnimatedImage_reset(SkAnimatedImage* self) {
+    self->reset();
+}
+
+extern "C" bool C_SkAnimatedImage_isFinished(const SkAnimatedImage* self) {
+    return self->isFinished();
+}
+
+extern "C" int C_SkAnimatedImage_decodeNextFrame(SkAnimatedImage* self) {
+    return self->decodeNextFrame();
+}
+
+extern "C" void C_SkAnimatedImage_setRepetitionCount(SkAnimatedImage* self, int count) {
+    self->setRepetitionCount(count);
+}
+
+extern "C" int C_SkAnimatedImage_getRepetitionCount(const SkAnimatedImage* self) {
+    return self->getRepetitionCount();
+}
+
 //
 // core/SkSurface.h
 //
@@ -469,8 +679,8 @@ extern "C" void C_SkImage_getBackendTexture(
     *result = self->getBackendTexture(flushPendingGrContextIO, origin);
 }
 
-extern "C" SkData* C_SkImage_encodeToData(const SkImage* self, SkEncodedImageFormat imageFormat) {
-    return self->encodeToData(imageFormat, 100).release();
+extern "C" SkData* C_SkImage_encodeToData(const SkImage* self, SkEncodedImageFormat imageFormat, int quality) {
+    return self->encodeToData(imageFormat, quality).release();
 }
 
 extern "C" SkData* C_SkImage_refEncodedData(const SkImage* self) {
@@ -523,6 +733,79 @@ extern "C" SkData *C_SkEncodeBitmap(const SkBitmap *src, SkEncodedImageFormat fo
     return SkEncodeBitmap(*src, format, quality).release();
 }
 
+//
+// encode/SkEncoder.h
+//
+
+extern "C" void C_SkEncoder_delete(SkEncoder* self) {
+    delete self;
+}
+
+extern "C" bool C_SkEncoder_encodeRows(SkEncoder* self, int numRows) {
+    return self->encodeRows(numRows);
+}
+
+//
+// encode/SkPngEncoder.h
+//
+
+static SkPngEncoder::Options pngEncoderOptions(int filterFlags, int zlibLevel, const char* const* comments, size_t commentsCount) {
+    SkPngEncoder::Options options;
+    options.fFilterFlags = static_cast<SkPngEncoder::FilterFlag>(filterFlags);
+    options.fZLibLevel = zlibLevel;
+    if (commentsCount) {
+        std::unique_ptr<size_t[]> sizes(new size_t[commentsCount]);
+        for (size_t i = 0; i != commentsCount; ++i) {
+            sizes[i] = strlen(comments[i]) + 1;
+        }
+        options.fComments = SkDataTable::MakeCopyArrays(reinterpret_cast<const void* const*>(comments), sizes.get(), static_cast<int>(commentsCount));
+    }
+    return options;
+}
+
+extern "C" bool C_SkPngEncoder_Encode(SkWStream* dst, const SkPixmap* src, int filterFlags, int zlibLevel, const char* const* comments, size_t commentsCount) {
+    return SkPngEncoder::Encode(dst, *src, pngEncoderOptions(filterFlags, zlibLevel, comments, commentsCount));
+}
+
+extern "C" SkEncoder* C_SkPngEncoder_Make(SkWStream* dst, const SkPixmap* src, int filterFlags, int zlibLevel, const char* const* comments, size_t commentsCount) {
+    return SkPngEncoder::Make(dst, *src, pngEncoderOptions(filterFlags, zlibLevel, comments, commentsCount)).release();
+}
+
+//
+// encode/SkJpegEncoder.h
+//
+
+static SkJpegEncoder::Options jpegEncoderOptions(int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    SkJpegEncoder::Options options;
+    options.fQuality = quality;
+    options.fDownsample = downsample;
+    options.fAlphaOption = alphaOption;
+    return options;
+}
+
+extern "C" bool C_SkJpegEncoder_Encode(SkWStream* dst, const SkPixmap* src, int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    return SkJpegEncoder::Encode(dst, *src, jpegEncoderOptions(quality, downsample, alphaOption));
+}
+
+extern "C" SkEncoder* C_SkJpegEncoder_Make(SkWStream* dst, const SkPixmap* src, int quality, SkJpegEncoder::Downsample downsample, SkJpegEncoder::AlphaOption alphaOption) {
+    return SkJpegEncoder::Make(dst, *src, jpegEncoderOptions(quality, downsample, alphaOption)).release();
+}
+
+//
+// encode/SkWebpEncoder.h
+//
+
+#if defined(SK_HAS_WEBP_LIBRARY)
+
+extern "C" bool C_SkWebpEncoder_Encode(SkWStream* dst, const SkPixmap* src, SkWebpEncoder::Compression compression, float quality) {
+    SkWebpEncoder::Options options;
+    options.fCompression = compression;
+    options.fQuality = quality;
+    return SkWebpEncoder::Encode(dst, *src, options);
+}
+
+#endif
+
 //
 // core/SkData.h
 //
@@ -559,6 +842,18 @@ extern "C" SkData* C_SkData_MakeWithoutCopy(const void* data, size_t length) {
     return SkData::MakeWithoutCopy(data, length).release();
 }
 
+extern "C" SkData* C_SkData_MakeWithProc(const void* ptr, size_t length, SkData::ReleaseProc proc, void* ctx) {
+    return SkData::MakeWithProc(ptr, length, proc, ctx).release();
+}
+
+extern "C" SkData* C_SkData_MakeFromFileName(const char path[]) {
+    return SkData::MakeFromFileName(path).release();
+}
+
+extern "C" SkData* C_SkData_MakeFromStream(SkStream* stream, size_t size) {
+    return SkData::MakeFromStream(stream, size).release();
+}
+
 extern "C" SkData* C_SkData_MakeEmpty() {
     return SkData::MakeEmpty().release();
 }
@@ -1034,6 +1329,10 @@ extern "C" SkPicture* C_SkPicture_MakeFromData(const SkData* data) {
     return SkPicture::MakeFromData(data).release();
 }
 
+extern "C" SkPicture* C_SkPicture_MakeFromStream(SkStream* stream) {
+    return SkPicture::MakeFromStream(stream).release();
+}
+
 extern "C" SkPicture* C_SkPicture_MakeFromData2(const void* data, size_t size) {
     return SkPicture::MakeFromData(data, size).release();
 }
@@ -1265,6 +1564,11 @@ extern "C" SkTypeface* C_SkTypeface_MakeFromData(SkData* data, int index) {
     return SkTypeface::MakeFromData(sp(data), index).release();
 }
 
+// note: this function _consumes_ the stream.
+extern "C" SkTypeface* C_SkTypeface_MakeFromStream(SkStreamAsset* stream, int index) {
+    return SkTypeface::MakeFromStream(std::unique_ptr<SkStreamAsset>(stream), index).release();
+}
+
 extern "C" SkTypeface* C_SkTypeface_makeClone(const SkTypeface* self, const SkFontArguments* arguments) {
     return self->makeClone(*arguments).release();
 }
@@ -1327,6 +1631,10 @@ extern "C" SkData* C_SkFlattenable_serialize(const SkFlattenable* self) {
 // core/SkFont.h
 //
 
+extern "C" void C_SkFont_CopyConstruct(SkFont* uninitialized, const SkFont* font) {
+    new(uninitialized) SkFont(*font);
+}
+
 extern "C" void C_SkFont_ConstructFromTypeface(SkFont* uninitialized, SkTypeface* typeface) {
     new(uninitialized) SkFont(sp(typeface));
 }
@@ -1743,6 +2051,71 @@ extern "C" void C_SkDrawable_GpuDrawHandler_draw(SkDrawable::GpuDrawHandler *sel
     self->draw(*info);
 }
 
+//
+// core/SkExecutor.h
+//
+
+extern "C" void C_SkExecutor_delete(SkExecutor* self) {
+    delete self;
+}
+
+extern "C" SkExecutor* C_SkExecutor_MakeFIFOThreadPool(int threads) {
+    return SkExecutor::MakeFIFOThreadPool(threads).release();
+}
+
+extern "C" SkExecutor* C_SkExecutor_MakeLIFOThreadPool(int threads) {
+    return SkExecutor::MakeLIFOThreadPool(threads).release();
+}
+
+//
+// RustExecutor: public SkExecutor
+//
+
+namespace RustExecutorFns {
+    // work must be run or deleted with C_RustExecutor_runWork() or C_RustExecutor_deleteWork().
+    extern "C" typedef void (*Add)(void*, void* work);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class RustExecutor: public SkExecutor {
+
+public:
+    struct Param {
+        void* data;
+        RustExecutorFns::Add add;
+        RustExecutorFns::Drop drop;
+    };
+
+    explicit RustExecutor(const Param& param)
+    :_param(param){
+    }
+
+    ~RustExecutor() override {
+        _param.drop(_param.data);
+    }
+
+    void add(std::function<void(void)> work) override {
+        _param.add(_param.data, new std::function<void(void)>(std::move(work)));
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkExecutor* C_RustExecutor_new(const RustExecutor::Param* param) {
+    return new RustExecutor(*param);
+}
+
+extern "C" void C_RustExecutor_runWork(void* work) {
+    auto function = static_cast<std::function<void(void)>*>(work);
+    (*function)();
+    delete function;
+}
+
+extern "C" void C_RustExecutor_deleteWork(void* work) {
+    delete static_cast<std::function<void(void)>*>(work);
+}
+
 //
 // SkImageFilter
 //
@@ -1811,6 +2184,83 @@ extern "C" SkImageGenerator *C_SkImageGenerator_MakeFromPicture(
             sp(cs)).release();
 }
 
+//
+// RustImageGenerator: public SkImageGenerator
+//
+
+namespace RustImageGeneratorFns {
+    extern "C" typedef SkData* (*RefEncodedData)(void*);
+    extern "C" typedef bool (*GetPixels)(void*, const SkImageInfo*, void*, size_t);
+    extern "C" typedef bool (*QueryYUVA8)(void*, SkYUVASizeInfo*, SkYUVAIndex*, SkYUVColorSpace*);
+    extern "C" typedef bool (*GetYUVA8Planes)(void*, const SkYUVASizeInfo*, const SkYUVAIndex*, void**);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class RustImageGenerator: public SkImageGenerator {
+
+public:
+    struct Param {
+        void* data;
+        RustImageGeneratorFns::RefEncodedData refEncodedData;
+        RustImageGeneratorFns::GetPixels getPixels;
+        RustImageGeneratorFns::QueryYUVA8 queryYUVA8;
+        RustImageGeneratorFns::GetYUVA8Planes getYUVA8Planes;
+        RustImageGeneratorFns::Drop drop;
+    };
+
+    RustImageGenerator(const SkImageInfo& info, const Param& param)
+    :SkImageGenerator(info), _param(param){
+    }
+
+    ~RustImageGenerator() override {
+        _param.drop(_param.data);
+    }
+
+protected:
+    sk_sp<SkData> onRefEncodedData() override {
+        return sp(_param.refEncodedData(_param.data));
+    }
+
+    bool onGetPixels(const SkImageInfo& info, void* pixels, size_t rowBytes, const Options&) override {
+        return _param.getPixels(_param.data, &info, pixels, rowBytes);
+    }
+
+    bool onQueryYUVA8(SkYUVASizeInfo* sizeInfo, SkYUVAIndex indices[SkYUVAIndex::kIndexCount], SkYUVColorSpace* colorSpace) const override {
+        return _param.queryYUVA8(_param.data, sizeInfo, indices, colorSpace);
+    }
+
+    bool onGetYUVA8Planes(const SkYUVASizeInfo& sizeInfo, const SkYUVAIndex indices[SkYUVAIndex::kIndexCount], void* planes[]) override {
+        return _param.getYUVA8Planes(_param.data, &sizeInfo, indices, planes);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkImageGenerator* C_RustImageGenerator_new(const SkImageInfo* info, const RustImageGenerator::Param* param) {
+    return new RustImageGenerator(*info, *param);
+}
+
+//
+// core/SkGraphics.h
+//
+
+namespace ImageGeneratorFactoryFns {
+    extern "C" typedef SkImageGenerator* (*FromEncodedData)(SkData*);
+}
+
+static ImageGeneratorFactoryFns::FromEncodedData imageGeneratorFromEncodedDataFactory = nullptr;
+
+static std::unique_ptr<SkImageGenerator> makeImageGeneratorFromEncodedData(sk_sp<SkData> data) {
+    return std::unique_ptr<SkImageGenerator>(imageGeneratorFromEncodedDataFactory(data.release()));
+}
+
+// note: the factory takes ownership of the data it receives.
+extern "C" void C_SkGraphics_SetImageGeneratorFromEncodedDataFactory(ImageGeneratorFactoryFns::FromEncodedData factory) {
+    imageGeneratorFromEncodedDataFactory = factory;
+    SkGraphics::SetImageGeneratorFromEncodedDataFactory(factory ? makeImageGeneratorFromEncodedData : nullptr);
+}
+
 //
 // core/SkString.h
 //
@@ -1997,6 +2447,148 @@ extern "C" void C_SkStream_delete(SkStream* stream) {
     delete stream;
 }
 
+//
+// RustStream: public SkStream
+//
+
+namespace RustStreamFns {
+    extern "C" typedef size_t (*Read)(void*, void*, size_t);
+    extern "C" typedef size_t (*Peek)(void*, void*, size_t);
+    extern "C" typedef bool (*IsAtEnd)(void*);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class RustStream: public SkStream {
+
+public:
+    struct Param {
+        void* data;
+        RustStreamFns::Read read;
+        RustStreamFns::Peek peek;
+        RustStreamFns::IsAtEnd isAtEnd;
+        RustStreamFns::Drop drop;
+    };
+
+    explicit RustStream(const Param& param)
+    :_param(param){
+    }
+
+    ~RustStream() override {
+        _param.drop(_param.data);
+    }
+
+    size_t read(void* buffer, size_t size) override {
+        return _param.read(_param.data, buffer, size);
+    }
+
+    size_t peek(void* buffer, size_t size) const override {
+        return _param.peek(_param.data, buffer, size);
+    }
+
+    bool isAtEnd() const override {
+        return _param.isAtEnd(_param.data);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" SkStream* C_RustStream_new(const RustStream::Param* param) {
+    return new RustStream(*param);
+}
+
+//
+// RustStreamAsset: public SkStreamAsset
+//
+
+namespace RustStreamAssetFns {
+    extern "C" typedef size_t (*Read)(void*, void*, size_t);
+    extern "C" typedef bool (*Seek)(void*, size_t);
+    extern "C" typedef size_t (*GetPosition)(void*);
+    extern "C" typedef size_t (*GetLength)(void*);
+    extern "C" typedef void* (*Duplicate)(void*);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class RustStreamAsset: public SkStreamAsset {
+
+public:
+    struct Param {
+        void* data;
+        RustStreamAssetFns::Read read;
+        RustStreamAssetFns::Seek seek;
+        RustStreamAssetFns::GetPosition getPosition;
+        RustStreamAssetFns::GetLength getLength;
+        RustStreamAssetFns::Duplicate duplicate;
+        RustStreamAssetFns::Drop drop;
+    };
+
+    explicit RustStreamAsset(const Param& param)
+    :_param(param){
+    }
+
+    ~RustStreamAsset() override {
+        _param.drop(_param.data);
+    }
+
+    size_t read(void* buffer, size_t size) override {
+        return _param.read(_param.data, buffer, size);
+    }
+
+    size_t peek(void* buffer, size_t size) const override {
+        size_t position = getPosition();
+        size_t read = _param.read(_param.data, buffer, size);
+        _param.seek(_param.data, position);
+        return read;
+    }
+
+    bool isAtEnd() const override {
+        return getPosition() >= getLength();
+    }
+
+    bool rewind() override {
+        return _param.seek(_param.data, 0);
+    }
+
+    size_t getPosition() const override {
+        return _param.getPosition(_param.data);
+    }
+
+    bool seek(size_t position) override {
+        return _param.seek(_param.data, std::min(position, getLength()));
+    }
+
+    bool move(long offset) override {
+        long position = static_cast<long>(getPosition()) + offset;
+        return seek(static_cast<size_t>(std::max(position, 0L)));
+    }
+
+    size_t getLength() const override {
+        return _param.getLength(_param.data);
+    }
+
+private:
+    SkStreamAsset* onDuplicate() const override {
+        Param param = _param;
+        param.data = _param.duplicate(_param.data);
+        return param.data ? new RustStreamAsset(param) : nullptr;
+    }
+
+    SkStreamAsset* onFork() const override {
+        SkStreamAsset* fork = onDuplicate();
+        if (fork) {
+            fork->seek(getPosition());
+        }
+        return fork;
+    }
+
+    Param _param;
+};
+
+extern "C" SkStreamAsset* C_RustStreamAsset_new(const RustStreamAsset::Param* param) {
+    return new RustStreamAsset(*param);
+}
+
 //
 // SkWStream
 //
@@ -2009,6 +2601,53 @@ extern "C" bool C_SkWStream_write(SkWStream* self, const void* buffer, size_t si
     return self->write(buffer, size);
 }
 
+//
+// RustWStream: public SkWStream
+//
+
+namespace RustWStreamFns {
+    extern "C" typedef bool (*Write)(void*, const void*, size_t);
+    extern "C" typedef void (*Flush)(void*);
+}
+
+class RustWStream: public SkWStream {
+
+public:
+    struct Param {
+        void* data;
+        RustWStreamFns::Write write;
+        RustWStreamFns::Flush flush;
+    };
+
+    explicit RustWStream(const Param& param)
+    :_param(param), _bytesWritten(0) {
+    }
+
+    bool write(const void* buffer, size_t size) override {
+        if (!_param.write(_param.data, buffer, size)) {
+            return false;
+        }
+        _bytesWritten += size;
+        return true;
+    }
+
+    void flush() override {
+        _param.flush(_param.data);
+    }
+
+    size_t bytesWritten() const override {
+        return _bytesWritten;
+    }
+
+private:
+    Param _param;
+    size_t _bytesWritten;
+};
+
+extern "C" void C_RustWStream_construct(RustWStream* uninitialized, const RustWStream::Param* param) {
+    new(uninitialized) RustWStream(*param);
+}
+
 //
 // SkMemoryStream: public SkStreamMemory
 //
@@ -2709,6 +3348,10 @@ extern "C" SkDocument* C_SkPDF_MakeDocument(SkWStream* stream, const SkPDF::Meta
     return SkPDF::MakeDocument(stream, *metadata).release();
 }
 
+extern "C" void C_SkPDF_SetNodeId(SkCanvas* canvas, int nodeID) {
+    SkPDF::SetNodeId(canvas, nodeID);
+}
+
 //
 // GrBackendFormat
 //
@@ -12,6 +12,10 @@
 #include "modules/skparagraph/include/TextShadow.h"
 #include "modules/skparagraph/include/TextStyle.h"
 #include "modules/skparagraph/include/TypefaceFontProvider.h"
+#include "modules/skparagraph/src/ParagraphImpl.h"
+
+#include "unicode/ubrk.h"
+#include "unicode/utext.h"
 
 using namespace skia::textlayout;
 
@@ -246,6 +250,39 @@ extern "C" {
     void C_Paragraph_markDirty(Paragraph* self) {
         self->markDirty();
     }
+
+    // Paragraphs are always built by the ParagraphBuilderImpl, so they are ParagraphImpls.
+
+    const char* C_Paragraph_text(const Paragraph* self, size_t* len) {
+        auto text = static_cast<const ParagraphImpl*>(self)->text();
+        *len = text.size();
+        return text.begin();
+    }
+
+    // Returns the grapheme boundaries that precede and follow the UTF-8 offset. The
+    // beginning and the end of the text are used if there are none.
+    void C_Paragraph_getGraphemeBoundaries(const Paragraph* self, size_t offset, size_t range[2]) {
+        auto text = static_cast<const ParagraphImpl*>(self)->text();
+        range[0] = 0;
+        range[1] = text.size();
+
+        UErrorCode status = U_ZERO_ERROR;
+        UText* utext = utext_openUTF8(nullptr, text.begin(), text.size(), &status);
+        UBreakIterator* iterator = ubrk_open(UBRK_CHARACTER, uloc_getDefault(), nullptr, 0, &status);
+        ubrk_setUText(iterator, utext, &status);
+        if (U_SUCCESS(status)) {
+            int32_t preceding = ubrk_preceding(iterator, SkToS32(offset));
+            if (preceding != UBRK_DONE) {
+                range[0] = preceding;
+            }
+            int32_t following = ubrk_following(iterator, SkToS32(offset));
+            if (following != UBRK_DONE) {
+                range[1] = following;
+            }
+        }
+        ubrk_close(iterator);
+        utext_close(utext);
+    }
 }
 
 //
@@ -0,0 +1,64 @@
+/// Skia particles Module C Wrapper Functions
+
+#include "bindings.h"
+
+#include "include/core/SkCanvas.h"
+#include "include/private/SkOnce.h"
+#include "include/utils/SkRandom.h"
+#include "modules/particles/include/SkParticleBinding.h"
+#include "modules/particles/include/SkParticleDrawable.h"
+#include "modules/particles/include/SkParticleEffect.h"
+#include "modules/particles/include/SkParticleSerialization.h"
+#include "src/utils/SkJSON.h"
+
+//
+// SkParticleEffectParams
+//
+
+extern "C" SkParticleEffectParams* C_SkParticleEffectParams_MakeFromJSON(const char* json, size_t length) {
+    // the reflected types must be registered before they can be deserialized.
+    static SkOnce registerTypes;
+    registerTypes([] {
+        SkParticleBinding::RegisterBindingTypes();
+        SkParticleDrawable::RegisterDrawableTypes();
+    });
+
+    skjson::DOM dom(json, length);
+    if (!dom.root().is<skjson::ObjectValue>()) {
+        return nullptr;
+    }
+
+    auto params = sk_make_sp<SkParticleEffectParams>();
+    SkFromJsonVisitor fromJson(dom.root());
+    params->visitFields(&fromJson);
+    return params.release();
+}
+
+//
+// SkParticleEffect
+//
+
+// note: this function _consumes_ the params.
+extern "C" SkParticleEffect* C_SkParticleEffect_new(SkParticleEffectParams* params, uint32_t seed) {
+    return new SkParticleEffect(sk_sp<SkParticleEffectParams>(params), SkRandom(seed));
+}
+
+extern "C" void C_SkParticleEffect_start(SkParticleEffect* self, double now, bool looping) {
+    self->start(now, looping);
+}
+
+extern "C" void C_SkParticleEffect_update(SkParticleEffect* self, double now) {
+    self->update(now);
+}
+
+extern "C" void C_SkParticleEffect_draw(SkParticleEffect* self, SkCanvas* canvas) {
+    self->draw(canvas);
+}
+
+extern "C" bool C_SkParticleEffect_isAlive(const SkParticleEffect* self) {
+    return self->isAlive();
+}
+
+extern "C" int C_SkParticleEffect_getCount(const SkParticleEffect* self) {
+    return self->getCount();
+}
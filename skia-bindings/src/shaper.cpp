@@ -49,6 +49,13 @@ extern "C" SkShaper::FontRunIterator* C_SkShaper_MakeFontMgrRunIterator(const ch
     return SkShaper::MakeFontMgrRunIterator(utf8, utf8Bytes, *font, sk_sp<SkFontMgr>(fallback)).release();
 }
 
+// note: the returned iterator refers to requestName and language.
+extern "C" SkShaper::FontRunIterator* C_SkShaper_MakeFontMgrRunIterator2(
+    const char* utf8, size_t utf8Bytes, const SkFont* font, SkFontMgr* fallback,
+    const char* requestName, const SkFontStyle* requestStyle, const SkShaper::LanguageRunIterator* language) {
+    return SkShaper::MakeFontMgrRunIterator(utf8, utf8Bytes, *font, sk_sp<SkFontMgr>(fallback), requestName, *requestStyle, language).release();
+}
+
 extern "C" SkShaper::FontRunIterator* C_SkShaper_TrivialFontRunIterator_new(const SkFont& font, size_t utf8Bytes) {
     return new SkShaper::TrivialFontRunIterator(font, utf8Bytes);
 }
@@ -166,6 +173,147 @@ extern "C" void C_RustRunHandler_construct(RustRunHandler* uninitialized, const
     new(uninitialized)RustRunHandler(*param);
 }
 
+//
+// Rust*RunIterator: public SkShaper::*RunIterator
+//
+
+namespace RunIterator {
+    extern "C" typedef void (*Consume)(TraitObject);
+    extern "C" typedef size_t (*EndOfCurrentRun)(TraitObject);
+    extern "C" typedef bool (*AtEnd)(TraitObject);
+    extern "C" typedef const SkFont* (*CurrentFont)(TraitObject);
+    extern "C" typedef uint8_t (*CurrentLevel)(TraitObject);
+    extern "C" typedef SkFourByteTag (*CurrentScript)(TraitObject);
+    extern "C" typedef const char* (*CurrentLanguage)(TraitObject);
+}
+
+struct RustRunIteratorParam {
+    TraitObject trait;
+    ::RunIterator::Consume consume;
+    ::RunIterator::EndOfCurrentRun endOfCurrentRun;
+    ::RunIterator::AtEnd atEnd;
+};
+
+template<typename Base>
+class RustRunIterator: public Base {
+
+public:
+    explicit RustRunIterator(const RustRunIteratorParam& param)
+    :_param(param){
+    }
+
+    void consume() override {
+        _param.consume(_param.trait);
+    }
+
+    size_t endOfCurrentRun() const override {
+        return _param.endOfCurrentRun(_param.trait);
+    }
+
+    bool atEnd() const override {
+        return _param.atEnd(_param.trait);
+    }
+
+protected:
+    RustRunIteratorParam _param;
+};
+
+class RustFontRunIterator: public RustRunIterator<SkShaper::FontRunIterator> {
+
+public:
+    struct Param {
+        RustRunIteratorParam iterator;
+        ::RunIterator::CurrentFont currentFont;
+    };
+
+    explicit RustFontRunIterator(const Param& param)
+    :RustRunIterator(param.iterator), _currentFont(param.currentFont){
+    }
+
+    const SkFont& currentFont() const override {
+        return *_currentFont(_param.trait);
+    }
+
+private:
+    ::RunIterator::CurrentFont _currentFont;
+};
+
+extern "C" SkShaper::FontRunIterator* C_RustFontRunIterator_new(const RustFontRunIterator::Param* param) {
+    return new RustFontRunIterator(*param);
+}
+
+class RustBiDiRunIterator: public RustRunIterator<SkShaper::BiDiRunIterator> {
+
+public:
+    struct Param {
+        RustRunIteratorParam iterator;
+        ::RunIterator::CurrentLevel currentLevel;
+    };
+
+    explicit RustBiDiRunIterator(const Param& param)
+    :RustRunIterator(param.iterator), _currentLevel(param.currentLevel){
+    }
+
+    uint8_t currentLevel() const override {
+        return _currentLevel(_param.trait);
+    }
+
+private:
+    ::RunIterator::CurrentLevel _currentLevel;
+};
+
+extern "C" SkShaper::BiDiRunIterator* C_RustBiDiRunIterator_new(const RustBiDiRunIterator::Param* param) {
+    return new RustBiDiRunIterator(*param);
+}
+
+class RustScriptRunIterator: public RustRunIterator<SkShaper::ScriptRunIterator> {
+
+public:
+    struct Param {
+        RustRunIteratorParam iterator;
+        ::RunIterator::CurrentScript currentScript;
+    };
+
+    explicit RustScriptRunIterator(const Param& param)
+    :RustRunIterator(param.iterator), _currentScript(param.currentScript){
+    }
+
+    SkFourByteTag currentScript() const override {
+        return _currentScript(_param.trait);
+    }
+
+private:
+    ::RunIterator::CurrentScript _currentScript;
+};
+
+extern "C" SkShaper::ScriptRunIterator* C_RustScriptRunIterator_new(const RustScriptRunIterator::Param* param) {
+    return new RustScriptRunIterator(*param);
+}
+
+class RustLanguageRunIterator: public RustRunIterator<SkShaper::LanguageRunIterator> {
+
+public:
+    struct Param {
+        RustRunIteratorParam iterator;
+        ::RunIterator::CurrentLanguage currentLanguage;
+    };
+
+    explicit RustLanguageRunIterator(const Param& param)
+    :RustRunIterator(param.iterator), _currentLanguage(param.currentLanguage){
+    }
+
+    const char* currentLanguage() const override {
+        return _currentLanguage(_param.trait);
+    }
+
+private:
+    ::RunIterator::CurrentLanguage _currentLanguage;
+};
+
+extern "C" SkShaper::LanguageRunIterator* C_RustLanguageRunIterator_new(const RustLanguageRunIterator::Param* param) {
+    return new RustLanguageRunIterator(*param);
+}
+
 extern "C" void
 C_SkShaper_shape(const SkShaper *self, const char *utf8, size_t utf8Bytes, const SkFont *srcFont, bool leftToRight,
                  SkScalar width, SkShaper::RunHandler *runHandler) {
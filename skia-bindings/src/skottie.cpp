@@ -0,0 +1,322 @@
+/// Skia skottie Module C Wrapper Functions
+
+#include "bindings.h"
+
+#include "include/core/SkCanvas.h"
+#include "include/core/SkData.h"
+#include "include/core/SkFontMgr.h"
+#include "include/core/SkImage.h"
+#include "modules/skottie/include/Skottie.h"
+#include "modules/skottie/include/SkottieProperty.h"
+
+//
+// skottie::Animation
+//
+
+extern "C" void C_skottie_Animation_ref(const skottie::Animation* self) {
+    self->ref();
+}
+
+extern "C" void C_skottie_Animation_unref(const skottie::Animation* self) {
+    self->unref();
+}
+
+extern "C" bool C_skottie_Animation_unique(const skottie::Animation* self) {
+    return self->unique();
+}
+
+extern "C" const SkString* C_skottie_Animation_version(const skottie::Animation* self) {
+    return &self->version();
+}
+
+extern "C" double C_skottie_Animation_duration(const skottie::Animation* self) {
+    return self->duration();
+}
+
+extern "C" double C_skottie_Animation_fps(const skottie::Animation* self) {
+    return self->fps();
+}
+
+extern "C" void C_skottie_Animation_size(const skottie::Animation* self, SkSize* size) {
+    *size = self->size();
+}
+
+extern "C" void C_skottie_Animation_seek(skottie::Animation* self, SkScalar t) {
+    self->seek(t);
+}
+
+extern "C" void C_skottie_Animation_render(const skottie::Animation* self, SkCanvas* canvas, const SkRect* dst) {
+    self->render(canvas, dst);
+}
+
+//
+// skottie::Animation::Builder
+//
+
+extern "C" skottie::Animation::Builder* C_skottie_Animation_Builder_new() {
+    return new skottie::Animation::Builder();
+}
+
+extern "C" void C_skottie_Animation_Builder_delete(skottie::Animation::Builder* self) {
+    delete self;
+}
+
+// note: this function _consumes_ the font manager.
+extern "C" void C_skottie_Animation_Builder_setFontManager(skottie::Animation::Builder* self, SkFontMgr* fontMgr) {
+    self->setFontManager(sk_sp<SkFontMgr>(fontMgr));
+}
+
+extern "C" skottie::Animation* C_skottie_Animation_Builder_make(skottie::Animation::Builder* self, const char* data, size_t length) {
+    return self->make(data, length).release();
+}
+
+//
+// RustLogger: public skottie::Logger
+//
+
+namespace RustLoggerFns {
+    // json may be nullptr.
+    extern "C" typedef void (*Log)(void*, skottie::Logger::Level level, const char* message, const char* json);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class RustLogger: public skottie::Logger {
+
+public:
+    struct Param {
+        void* data;
+        RustLoggerFns::Log log;
+        RustLoggerFns::Drop drop;
+    };
+
+    explicit RustLogger(const Param& param)
+    :_param(param){
+    }
+
+    ~RustLogger() override {
+        _param.drop(_param.data);
+    }
+
+    void log(Level level, const char message[], const char* json) override {
+        _param.log(_param.data, level, message, json);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" void C_skottie_Animation_Builder_setLogger(skottie::Animation::Builder* self, const RustLogger::Param* param) {
+    self->setLogger(sk_sp<skottie::Logger>(new RustLogger(*param)));
+}
+
+//
+// RustResourceProvider: public skottie::ResourceProvider
+//
+
+namespace RustResourceProviderFns {
+    // The functions return a new reference or nullptr if the resource is not available.
+    extern "C" typedef SkData* (*Load)(void*, const char* resourcePath, const char* resourceName);
+    extern "C" typedef SkImage* (*LoadImage)(void*, const char* resourcePath, const char* resourceName, const char* resourceId);
+    extern "C" typedef SkData* (*LoadFont)(void*, const char* name, const char* url);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class StaticImageAsset: public skottie::ImageAsset {
+
+public:
+    explicit StaticImageAsset(sk_sp<SkImage> image)
+    :_image(std::move(image)){
+    }
+
+    bool isMultiFrame() override {
+        return false;
+    }
+
+    sk_sp<SkImage> getFrame(float) override {
+        return _image;
+    }
+
+private:
+    sk_sp<SkImage> _image;
+};
+
+class RustResourceProvider: public skottie::ResourceProvider {
+
+public:
+    struct Param {
+        void* data;
+        RustResourceProviderFns::Load load;
+        RustResourceProviderFns::LoadImage loadImage;
+        RustResourceProviderFns::LoadFont loadFont;
+        RustResourceProviderFns::Drop drop;
+    };
+
+    explicit RustResourceProvider(const Param& param)
+    :_param(param){
+    }
+
+    ~RustResourceProvider() override {
+        _param.drop(_param.data);
+    }
+
+    sk_sp<SkData> load(const char resource_path[], const char resource_name[]) const override {
+        return sk_sp<SkData>(_param.load(_param.data, resource_path, resource_name));
+    }
+
+    sk_sp<skottie::ImageAsset> loadImageAsset(const char resource_path[], const char resource_name[], const char resource_id[]) const override {
+        auto image = _param.loadImage(_param.data, resource_path, resource_name, resource_id);
+        if (!image) {
+            return nullptr;
+        }
+        return sk_make_sp<StaticImageAsset>(sk_sp<SkImage>(image));
+    }
+
+    sk_sp<SkData> loadFont(const char name[], const char url[]) const override {
+        return sk_sp<SkData>(_param.loadFont(_param.data, name, url));
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" void C_skottie_Animation_Builder_setResourceProvider(skottie::Animation::Builder* self, const RustResourceProvider::Param* param) {
+    self->setResourceProvider(sk_sp<skottie::ResourceProvider>(new RustResourceProvider(*param)));
+}
+
+//
+// modules/skottie/include/SkottieProperty.h
+//
+
+// The property handles are passed as void* to keep bindgen away from the PropertyHandle template.
+
+extern "C" void C_skottie_ColorPropertyHandle_delete(void* self) {
+    delete static_cast<skottie::ColorPropertyHandle*>(self);
+}
+
+extern "C" SkColor C_skottie_ColorPropertyHandle_get(const void* self) {
+    return static_cast<const skottie::ColorPropertyHandle*>(self)->get();
+}
+
+extern "C" void C_skottie_ColorPropertyHandle_set(void* self, SkColor color) {
+    static_cast<skottie::ColorPropertyHandle*>(self)->set(color);
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_delete(void* self) {
+    delete static_cast<skottie::OpacityPropertyHandle*>(self);
+}
+
+extern "C" float C_skottie_OpacityPropertyHandle_get(const void* self) {
+    return static_cast<const skottie::OpacityPropertyHandle*>(self)->get();
+}
+
+extern "C" void C_skottie_OpacityPropertyHandle_set(void* self, float opacity) {
+    static_cast<skottie::OpacityPropertyHandle*>(self)->set(opacity);
+}
+
+extern "C" void C_skottie_TransformPropertyHandle_delete(void* self) {
+    delete static_cast<skottie::TransformPropertyHandle*>(self);
+}
+
+extern "C" void C_skottie_TransformPropertyHandle_get(const void* self, skottie::TransformPropertyValue* value) {
+    *value = static_cast<const skottie::TransformPropertyHandle*>(self)->get();
+}
+
+extern "C" void C_skottie_TransformPropertyHandle_set(void* self, const skottie::TransformPropertyValue* value) {
+    static_cast<skottie::TransformPropertyHandle*>(self)->set(*value);
+}
+
+extern "C" void C_skottie_TextPropertyHandle_delete(void* self) {
+    delete static_cast<skottie::TextPropertyHandle*>(self);
+}
+
+extern "C" void C_skottie_TextPropertyHandle_get(
+    const void* self,
+    SkTypeface** typeface, SkString* text, float* textSize, SkColor* fillColor, SkColor* strokeColor) {
+    auto value = static_cast<const skottie::TextPropertyHandle*>(self)->get();
+    *typeface = value.fTypeface.release();
+    *text = value.fText;
+    *textSize = value.fTextSize;
+    *fillColor = value.fFillColor;
+    *strokeColor = value.fStrokeColor;
+}
+
+// note: this function _consumes_ the typeface.
+extern "C" void C_skottie_TextPropertyHandle_set(
+    void* self,
+    SkTypeface* typeface, const char* text, size_t textLength, float textSize, SkColor fillColor, SkColor strokeColor) {
+    auto handle = static_cast<skottie::TextPropertyHandle*>(self);
+    // keep the properties that are not exposed, like alignment and the text box.
+    auto value = handle->get();
+    value.fTypeface = sk_sp<SkTypeface>(typeface);
+    value.fText = SkString(text, textLength);
+    value.fTextSize = textSize;
+    value.fFillColor = fillColor;
+    value.fStrokeColor = strokeColor;
+    handle->set(value);
+}
+
+//
+// RustPropertyObserver: public skottie::PropertyObserver
+//
+
+namespace RustPropertyObserverFns {
+    // The handles are owned by the callee and must be deleted with the matching
+    // C_skottie_*PropertyHandle_delete() function.
+    extern "C" typedef void (*OnProperty)(void*, const char* nodeName, void* handle);
+    extern "C" typedef void (*OnNode)(void*, const char* nodeName);
+    extern "C" typedef void (*Drop)(void*);
+}
+
+class RustPropertyObserver: public skottie::PropertyObserver {
+
+public:
+    struct Param {
+        void* data;
+        RustPropertyObserverFns::OnProperty onColorProperty;
+        RustPropertyObserverFns::OnProperty onOpacityProperty;
+        RustPropertyObserverFns::OnProperty onTextProperty;
+        RustPropertyObserverFns::OnProperty onTransformProperty;
+        RustPropertyObserverFns::OnNode onEnterNode;
+        RustPropertyObserverFns::OnNode onLeavingNode;
+        RustPropertyObserverFns::Drop drop;
+    };
+
+    explicit RustPropertyObserver(const Param& param)
+    :_param(param){
+    }
+
+    ~RustPropertyObserver() override {
+        _param.drop(_param.data);
+    }
+
+    void onColorProperty(const char node_name[], const LazyHandle<skottie::ColorPropertyHandle>& lh) override {
+        _param.onColorProperty(_param.data, node_name, lh().release());
+    }
+
+    void onOpacityProperty(const char node_name[], const LazyHandle<skottie::OpacityPropertyHandle>& lh) override {
+        _param.onOpacityProperty(_param.data, node_name, lh().release());
+    }
+
+    void onTextProperty(const char node_name[], const LazyHandle<skottie::TextPropertyHandle>& lh) override {
+        _param.onTextProperty(_param.data, node_name, lh().release());
+    }
+
+    void onTransformProperty(const char node_name[], const LazyHandle<skottie::TransformPropertyHandle>& lh) override {
+        _param.onTransformProperty(_param.data, node_name, lh().release());
+    }
+
+    void onEnterNode(const char node_name[]) override {
+        _param.onEnterNode(_param.data, node_name);
+    }
+
+    void onLeavingNode(const char node_name[]) override {
+        _param.onLeavingNode(_param.data, node_name);
+    }
+
+private:
+    Param _param;
+};
+
+extern "C" void C_skottie_Animation_Builder_setPropertyObserver(skottie::Animation::Builder* self, const RustPropertyObserver::Param* param) {
+    self->setPropertyObserver(sk_sp<skottie::PropertyObserver>(new RustPropertyObserver(*param)));
+}
@@ -0,0 +1,2 @@
+mod animated_image;
+pub use animated_image::*;
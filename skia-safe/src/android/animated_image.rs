@@ -0,0 +1,87 @@
+use crate::prelude::*;
+use crate::{Canvas, Codec, Drawable, Matrix, Rect};
+use skia_bindings as sb;
+use skia_bindings::{SkAnimatedImage, SkDrawable, SkRefCntBase};
+use std::mem;
+
+/// Thread unsafe drawable for drawing animated images (e.g. GIF).
+pub type AnimatedImage = RCHandle<SkAnimatedImage>;
+
+impl NativeRefCountedBase for SkAnimatedImage {
+    type Base = SkRefCntBase;
+}
+
+impl NativeBase<SkDrawable> for SkAnimatedImage {}
+
+impl RCHandle<SkAnimatedImage> {
+    /// Creates an animated image that decodes the frames of `codec`.
+    pub fn from_codec(mut codec: Codec) -> Option<AnimatedImage> {
+        let animated_image =
+            AnimatedImage::from_ptr(unsafe { sb::C_SkAnimatedImage_Make(codec.native_mut()) });
+        mem::forget(codec);
+        animated_image
+    }
+
+    /// Resets the animation to the beginning.
+    pub fn reset(&mut self) {
+        unsafe { sb::C_SkAnimatedImage_reset(self.native_mut()) }
+    }
+
+    /// Whether the animation completed.
+    ///
+    /// Animations may loop forever, in which case this will return `false`.
+    pub fn is_finished(&self) -> bool {
+        unsafe { sb::C_SkAnimatedImage_isFinished(self.native()) }
+    }
+
+    /// Decodes the next frame.
+    ///
+    /// Returns the number of milliseconds until the following frame should be decoded, or
+    /// `None` if the animation is finished.
+    pub fn decode_next_frame(&mut self) -> Option<usize> {
+        let duration = unsafe { sb::C_SkAnimatedImage_decodeNextFrame(self.native_mut()) };
+        duration.try_into().ok()
+    }
+
+    /// Sets the number of times to repeat the animation, or
+    /// [`crate::codec::REPETITION_COUNT_INFINITE`].
+    pub fn set_repetition_count(&mut self, count: i32) {
+        unsafe { sb::C_SkAnimatedImage_setRepetitionCount(self.native_mut(), count) }
+    }
+
+    pub fn repetition_count(&self) -> i32 {
+        unsafe { sb::C_SkAnimatedImage_getRepetitionCount(self.native()) }
+    }
+
+    /// Returns a [`Drawable`] that shares the state of this animated image and draws its
+    /// current frame.
+    pub fn to_drawable(&self) -> Drawable {
+        Drawable::from_unshared_ptr(unsafe { self.native_mut_force() } as *mut SkDrawable).unwrap()
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.to_drawable().bounds()
+    }
+
+    /// Draws the current frame.
+    pub fn draw(&self, canvas: &mut Canvas, matrix: Option<&Matrix>) {
+        self.to_drawable().draw(canvas, matrix)
+    }
+}
+
+#[test]
+fn animated_image_advances_frames() {
+    use crate::codec::codec::tests::ANIMATED_GIF;
+    use crate::{Color, Surface};
+
+    let codec = Codec::from_data(crate::Data::new_copy(ANIMATED_GIF)).unwrap();
+    let mut animated_image = AnimatedImage::from_codec(codec).unwrap();
+    let mut surface = Surface::new_raster_n32_premul((4, 4)).unwrap();
+
+    animated_image.draw(surface.canvas(), None);
+    let image = surface.image_snapshot();
+    assert_eq!(Color::RED, image.peek_pixels().unwrap().get_color((2, 2)));
+
+    assert!(animated_image.decode_next_frame().is_some());
+    assert!(!animated_image.is_finished());
+}
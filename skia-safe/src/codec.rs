@@ -1,4 +1,30 @@
-// TODO: wrap SkAndroidCodec.h, SkCodec.h, SkCodecAnimation.h
+//! Decoding of encoded images.
+//!
+//! The types that are not exported at the crate root are accessible through this module:
+//!
+//! ```no_run
+//! use skia_safe::codec::{
+//!     thumbnail, Codec, Frame, FrameInfo, Frames, IncrementalDecoder, Options, Result,
+//!     Scanline, ScanlineOrder, Scanlines, ZeroInitialized, NO_FRAME, REPETITION_COUNT_INFINITE,
+//! };
+//! use skia_safe::{AndroidCodec, AndroidOptions, Data, EncodedOrigin};
+//!
+//! fn first_frame(data: Data) -> Option<FrameInfo> {
+//!     let mut codec = Codec::from_data(data)?;
+//!     let _: Frames = codec.frames(None);
+//!     codec.frame_info(0)
+//! }
+//! ```
+
+mod android_codec;
+pub use android_codec::{thumbnail, AndroidCodec, AndroidOptions};
+
+#[allow(clippy::module_inception)]
+pub(crate) mod codec;
+pub use self::codec::*;
+
+mod codec_animation;
+pub use codec_animation::*;
 
 mod encoded_origin;
 pub use encoded_origin::*;
@@ -0,0 +1,284 @@
+use crate::codec::{Result, ZeroInitialized};
+use crate::prelude::*;
+use crate::{
+    AlphaType, Codec, ColorType, Data, EncodedImageFormat, EncodedOrigin, FilterQuality, IRect,
+    ISize, Image, ImageInfo, Pixmap,
+};
+use skia_bindings as sb;
+use skia_bindings::SkAndroidCodec;
+
+/// Additional options to pass to [`AndroidCodec::get_android_pixels()`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AndroidOptions {
+    pub zero_initialized: ZeroInitialized,
+    /// The subset of the image to decode, which must be supported by the codec, see
+    /// [`AndroidCodec::supported_subset()`].
+    pub subset: Option<IRect>,
+    /// Decode only every `sample_size`th pixel in each dimension.
+    pub sample_size: usize,
+}
+
+impl Default for AndroidOptions {
+    fn default() -> Self {
+        AndroidOptions {
+            zero_initialized: ZeroInitialized::default(),
+            subset: None,
+            sample_size: 1,
+        }
+    }
+}
+
+/// A codec that supports decoding at reduced sizes by sampling, and decoding subsets of the
+/// encoded image.
+pub type AndroidCodec = RefHandle<SkAndroidCodec>;
+unsafe impl Send for AndroidCodec {}
+
+impl NativeDrop for SkAndroidCodec {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkAndroidCodec_delete(self) }
+    }
+}
+
+impl RefHandle<SkAndroidCodec> {
+    pub fn from_codec(mut codec: Codec) -> Option<AndroidCodec> {
+        let ptr = unsafe { sb::C_SkAndroidCodec_MakeFromCodec(codec.native_mut()) };
+        std::mem::forget(codec);
+        AndroidCodec::from_ptr(ptr)
+    }
+
+    /// Creates a codec for the encoded image in `data`.
+    ///
+    /// Returns `None` if the format of the image is not recognized.
+    pub fn from_data(data: Data) -> Option<AndroidCodec> {
+        AndroidCodec::from_ptr(unsafe { sb::C_SkAndroidCodec_MakeFromData(data.into_ptr()) })
+    }
+
+    pub fn info(&self) -> ImageInfo {
+        ImageInfo::construct(|ii| unsafe { sb::C_SkAndroidCodec_getInfo(self.native(), ii) })
+    }
+
+    pub fn dimensions(&self) -> ISize {
+        self.info().dimensions()
+    }
+
+    pub fn encoded_format(&self) -> EncodedImageFormat {
+        EncodedImageFormat::from_native(unsafe {
+            sb::C_SkAndroidCodec_getEncodedFormat(self.native())
+        })
+    }
+
+    pub fn origin(&self) -> EncodedOrigin {
+        EncodedOrigin::from_native(unsafe { sb::C_SkAndroidCodec_getOrigin(self.native()) })
+    }
+
+    /// Returns the color type that best matches `requested_color_type` for this image.
+    pub fn compute_output_color_type(&mut self, requested_color_type: ColorType) -> ColorType {
+        ColorType::from_native(unsafe {
+            sb::C_SkAndroidCodec_computeOutputColorType(
+                self.native_mut(),
+                requested_color_type.into_native(),
+            )
+        })
+    }
+
+    pub fn compute_output_alpha_type(&mut self, requested_unpremul: bool) -> AlphaType {
+        AlphaType::from_native(unsafe {
+            sb::C_SkAndroidCodec_computeOutputAlphaType(self.native_mut(), requested_unpremul)
+        })
+    }
+
+    /// Returns the sample size that decodes the image as close as possible to `size`, and
+    /// updates `size` to the dimensions the image will have.
+    pub fn compute_sample_size(&self, size: &mut ISize) -> usize {
+        unsafe { sb::C_SkAndroidCodec_computeSampleSize(self.native(), size.native_mut()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns the dimensions of the image when it is decoded with `sample_size`.
+    pub fn sampled_dimensions(&self, sample_size: usize) -> ISize {
+        ISize::from_native(unsafe {
+            sb::C_SkAndroidCodec_getSampledDimensions(
+                self.native(),
+                sample_size.try_into().unwrap(),
+            )
+        })
+    }
+
+    /// Returns a subset that is supported by the codec and is as close as possible to
+    /// `desired_subset`.
+    pub fn supported_subset(&self, desired_subset: impl AsRef<IRect>) -> Option<IRect> {
+        let mut subset = *desired_subset.as_ref();
+        unsafe { sb::C_SkAndroidCodec_getSupportedSubset(self.native(), subset.native_mut()) }
+            .if_true_some(subset)
+    }
+
+    /// Returns the dimensions of `subset` when it is decoded with `sample_size`.
+    pub fn sampled_subset_dimensions(
+        &self,
+        sample_size: usize,
+        subset: impl AsRef<IRect>,
+    ) -> ISize {
+        ISize::from_native(unsafe {
+            sb::C_SkAndroidCodec_getSampledSubsetDimensions(
+                self.native(),
+                sample_size.try_into().unwrap(),
+                subset.as_ref().native(),
+            )
+        })
+    }
+
+    /// Decodes the image into `pixels`, which must be large enough to hold `info.height()`
+    /// rows of `row_bytes` each.
+    ///
+    /// The dimensions of `info` must match the sampled dimensions of the image or the subset
+    /// specified in `options`.
+    #[must_use]
+    pub fn get_android_pixels(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &mut [u8],
+        row_bytes: usize,
+        options: Option<&AndroidOptions>,
+    ) -> Result {
+        assert!(info.valid_row_bytes(row_bytes));
+        assert!(pixels.len() >= info.compute_byte_size(row_bytes));
+        let mut options = options.cloned().unwrap_or_default();
+        Result::from_native(unsafe {
+            sb::C_SkAndroidCodec_getAndroidPixels(
+                self.native_mut(),
+                info.native(),
+                pixels.as_mut_ptr() as _,
+                row_bytes,
+                options.zero_initialized.into_native(),
+                options
+                    .subset
+                    .as_mut()
+                    .map(|s| s.native_mut() as *mut _)
+                    .unwrap_or(std::ptr::null_mut()),
+                options.sample_size.try_into().unwrap(),
+            )
+        })
+    }
+
+    /// Decodes the image at full size into `pixels`.
+    #[must_use]
+    pub fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> Result {
+        self.get_android_pixels(info, pixels, row_bytes, None)
+    }
+
+    /// Decodes the image with `sample_size` into a raster image.
+    pub fn decode_sampled(&mut self, sample_size: usize) -> Option<Image> {
+        let info = ImageInfo::new_n32_premul(
+            self.sampled_dimensions(sample_size),
+            self.info().color_space(),
+        );
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        let options = AndroidOptions {
+            sample_size,
+            ..AndroidOptions::default()
+        };
+        match self.get_android_pixels(&info, &mut pixels, row_bytes, Some(&options)) {
+            Result::Success | Result::IncompleteInput => {}
+            _ => return None,
+        }
+        Image::from_raster_data(&info, Data::from_vec(pixels), row_bytes)
+    }
+}
+
+/// Decodes the encoded image in `data` into a raster image that fits into `max_size` while
+/// keeping its aspect ratio.
+///
+/// The image is decoded with the largest sample size that does not drop below the size of the
+/// thumbnail, which is much faster than decoding it at full size. Images smaller than `max_size`
+/// are not enlarged. The [`EncodedOrigin`] of the image is applied, so the thumbnail is upright.
+pub fn thumbnail(data: Data, max_size: impl Into<ISize>) -> Option<Image> {
+    let max_size = max_size.into();
+    if max_size.width <= 0 || max_size.height <= 0 {
+        return None;
+    }
+    let mut codec = AndroidCodec::from_data(data)?;
+    let origin = codec.origin();
+    let size = codec.dimensions();
+    // fit the upright image into max_size.
+    let max_size = origin.upright_dimensions(max_size);
+
+    let scale = (max_size.width as f32 / size.width as f32)
+        .min(max_size.height as f32 / size.height as f32)
+        .min(1.0);
+    let target = ISize::new(
+        ((size.width as f32 * scale).round() as i32).max(1),
+        ((size.height as f32 * scale).round() as i32).max(1),
+    );
+
+    let mut sample_size = ((1.0 / scale) as usize).max(1);
+    let mut sampled = codec.sampled_dimensions(sample_size);
+    while sample_size > 1 && (sampled.width < target.width || sampled.height < target.height) {
+        sample_size -= 1;
+        sampled = codec.sampled_dimensions(sample_size);
+    }
+
+    let image = codec.decode_sampled(sample_size)?;
+    if sampled == target {
+        return origin.apply_to_image(&image);
+    }
+
+    let info = image.image_info().with_dimensions(target);
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+    let pixmap = Pixmap::new_mut(&info, &mut pixels, row_bytes);
+    if !image.scale_pixels(&pixmap, FilterQuality::Medium, None) {
+        return None;
+    }
+    drop(pixmap);
+    let image = Image::from_raster_data(&info, Data::from_vec(pixels), row_bytes)?;
+    origin.apply_to_image(&image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{thumbnail, AndroidCodec};
+    use crate::codec::codec::tests::{encoded_jpeg_halves, with_exif_orientation};
+    use crate::{Color, Data, EncodedImageFormat, ISize, Surface};
+
+    fn encoded_jpeg(size: (i32, i32)) -> crate::Data {
+        let mut surface = Surface::new_raster_n32_premul(size).unwrap();
+        surface.canvas().clear(Color::BLUE);
+        surface
+            .image_snapshot()
+            .encode_to_data(EncodedImageFormat::JPEG)
+            .unwrap()
+    }
+
+    #[test]
+    fn android_codec_reports_sampled_dimensions() {
+        let codec = AndroidCodec::from_data(encoded_jpeg((64, 32))).unwrap();
+        assert_eq!(EncodedImageFormat::JPEG, codec.encoded_format());
+        assert_eq!(ISize::new(64, 32), codec.sampled_dimensions(1));
+        assert_eq!(ISize::new(32, 16), codec.sampled_dimensions(2));
+        assert_eq!(ISize::new(8, 4), codec.sampled_dimensions(8));
+    }
+
+    #[test]
+    fn android_codec_decodes_sampled() {
+        let mut codec = AndroidCodec::from_data(encoded_jpeg((64, 32))).unwrap();
+        let image = codec.decode_sampled(4).unwrap();
+        assert_eq!(ISize::new(16, 8), image.dimensions());
+    }
+
+    #[test]
+    fn thumbnail_fits_into_max_size() {
+        let image = thumbnail(encoded_jpeg((200, 100)), (30, 30)).unwrap();
+        assert_eq!(ISize::new(30, 15), image.dimensions());
+        let image = thumbnail(encoded_jpeg((20, 10)), (30, 30)).unwrap();
+        assert_eq!(ISize::new(20, 10), image.dimensions());
+    }
+
+    #[test]
+    fn thumbnail_is_upright() {
+        let jpeg = with_exif_orientation(&encoded_jpeg_halves(), 6);
+        let image = thumbnail(Data::new_copy(&jpeg), (4, 100)).unwrap();
+        assert_eq!(ISize::new(4, 8), image.dimensions());
+    }
+}
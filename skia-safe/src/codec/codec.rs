@@ -0,0 +1,992 @@
+use crate::interop::{Stream, StreamAsset};
+use crate::prelude::*;
+use crate::{
+    codec_animation, AlphaType, ColorSpace, ColorType, Data, EncodedImageFormat, EncodedOrigin,
+    IRect, ISize, Image, ImageInfo,
+};
+use skia_bindings as sb;
+use skia_bindings::{
+    SkCodec, SkCodec_FrameInfo, SkCodec_Options, SkCodec_Result, SkCodec_SkScanlineOrder,
+    SkCodec_ZeroInitialized,
+};
+use std::ffi::CStr;
+use std::sync::{Arc, Mutex};
+use std::{io, ptr, slice};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum Result {
+    Success = SkCodec_Result::kSuccess as _,
+    IncompleteInput = SkCodec_Result::kIncompleteInput as _,
+    ErrorInInput = SkCodec_Result::kErrorInInput as _,
+    InvalidConversion = SkCodec_Result::kInvalidConversion as _,
+    InvalidScale = SkCodec_Result::kInvalidScale as _,
+    InvalidParameters = SkCodec_Result::kInvalidParameters as _,
+    InvalidInput = SkCodec_Result::kInvalidInput as _,
+    CouldNotRewind = SkCodec_Result::kCouldNotRewind as _,
+    InternalError = SkCodec_Result::kInternalError as _,
+    Unimplemented = SkCodec_Result::kUnimplemented as _,
+}
+
+impl NativeTransmutable<SkCodec_Result> for Result {}
+#[test]
+fn test_result_layout() {
+    Result::test_layout()
+}
+
+impl Result {
+    pub fn is_success(self) -> bool {
+        self == Result::Success
+    }
+
+    /// Returns a textual representation of the result, as provided by Skia.
+    pub fn to_str(self) -> &'static str {
+        unsafe { CStr::from_ptr(sb::C_SkCodec_ResultToString(self.into_native())) }
+            .to_str()
+            .unwrap()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum ZeroInitialized {
+    Yes = SkCodec_ZeroInitialized::kYes_ZeroInitialized as _,
+    No = SkCodec_ZeroInitialized::kNo_ZeroInitialized as _,
+}
+
+impl NativeTransmutable<SkCodec_ZeroInitialized> for ZeroInitialized {}
+#[test]
+fn test_zero_initialized_layout() {
+    ZeroInitialized::test_layout()
+}
+
+impl Default for ZeroInitialized {
+    fn default() -> Self {
+        ZeroInitialized::No
+    }
+}
+
+/// The order in which the rows of an image are returned by [`Codec::get_scanlines()`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum ScanlineOrder {
+    TopDown = SkCodec_SkScanlineOrder::kTopDown_SkScanlineOrder as _,
+    BottomUp = SkCodec_SkScanlineOrder::kBottomUp_SkScanlineOrder as _,
+}
+
+impl NativeTransmutable<SkCodec_SkScanlineOrder> for ScanlineOrder {}
+#[test]
+fn test_scanline_order_layout() {
+    ScanlineOrder::test_layout()
+}
+
+/// Used in [`FrameInfo::required_frame`] to indicate that a frame does not depend on a
+/// prior frame.
+pub const NO_FRAME: i32 = -1;
+
+/// Returned by [`Codec::repetition_count()`] if the animation should repeat forever.
+pub const REPETITION_COUNT_INFINITE: i32 = -1;
+
+/// Additional options to pass to [`Codec::get_pixels()`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Options {
+    pub zero_initialized: ZeroInitialized,
+    pub subset: Option<IRect>,
+    pub frame_index: usize,
+    pub prior_frame: Option<usize>,
+}
+
+impl Options {
+    /// Creates the native options, which refer to the subset of `self`.
+    pub(crate) fn to_native(&self) -> SkCodec_Options {
+        SkCodec_Options {
+            fZeroInitialized: self.zero_initialized.into_native(),
+            fSubset: self
+                .subset
+                .as_ref()
+                .map(|s| s.native() as *const _)
+                .unwrap_or(ptr::null()),
+            fFrameIndex: self.frame_index.try_into().unwrap(),
+            fPriorFrame: self
+                .prior_frame
+                .map(|f| f.try_into().unwrap())
+                .unwrap_or(NO_FRAME),
+        }
+    }
+}
+
+/// Information about an individual frame of a multi-frame image.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct FrameInfo {
+    /// The frame that this frame needs to be blended with, or [`NO_FRAME`] if this frame is
+    /// independent.
+    pub required_frame: i32,
+    /// Number of milliseconds to show this frame.
+    pub duration: i32,
+    /// Whether the end marker for this frame is contained in the stream.
+    pub fully_received: bool,
+    /// This is conservative; it will still return non-opaque if e.g. a color index-based frame
+    /// has a color with alpha but does not use it.
+    pub alpha_type: AlphaType,
+    /// How this frame should be modified before decoding the next one.
+    pub disposal_method: codec_animation::DisposalMethod,
+}
+
+impl NativeTransmutable<SkCodec_FrameInfo> for FrameInfo {}
+#[test]
+fn test_frame_info_layout() {
+    FrameInfo::test_layout()
+}
+
+impl Default for FrameInfo {
+    fn default() -> Self {
+        FrameInfo {
+            required_frame: NO_FRAME,
+            duration: 0,
+            fully_received: false,
+            alpha_type: AlphaType::Unknown,
+            disposal_method: codec_animation::DisposalMethod::default(),
+        }
+    }
+}
+
+pub type Codec = RefHandle<SkCodec>;
+unsafe impl Send for Codec {}
+
+impl NativeDrop for SkCodec {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkCodec_delete(self) }
+    }
+}
+
+impl RefHandle<SkCodec> {
+    /// Creates a codec for the encoded image in `data`.
+    ///
+    /// Returns `None` if the format of the image is not recognized.
+    pub fn from_data(data: Data) -> Option<Codec> {
+        Codec::from_ptr(unsafe { sb::C_SkCodec_MakeFromData(data.into_ptr()) })
+    }
+
+    /// Creates a codec that decodes the encoded image in `bytes` without copying it.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Borrows<Codec>> {
+        let data = Data::from_ptr(unsafe {
+            sb::C_SkData_MakeWithoutCopy(bytes.as_ptr() as _, bytes.len())
+        })
+        .unwrap();
+        Codec::from_data(data).map(|codec| codec.borrows(bytes))
+    }
+
+    /// Creates a codec that reads the encoded image from `reader` while decoding.
+    ///
+    /// The reader is never rewound, so the image can be decoded only once. Returns the reason
+    /// if no codec could be created, for example [`Result::IncompleteInput`] if the reader did not
+    /// provide enough bytes to read the header.
+    pub fn from_read(reader: impl io::Read + Send + 'static) -> std::result::Result<Codec, Result> {
+        let stream = Stream::from_read(reader);
+        let mut result = Result::Success;
+        Codec::from_ptr(unsafe {
+            sb::C_SkCodec_MakeFromStream(stream.into_ptr(), result.native_mut())
+        })
+        .ok_or(result)
+    }
+
+    /// Creates a codec that reads the encoded image from `reader` while decoding.
+    ///
+    /// Unlike [`Self::from_read()`], the reader is rewound when needed, so the image can be
+    /// decoded multiple times. Returns [`Result::CouldNotRewind`] if the reader can not be
+    /// seeked.
+    pub fn from_stream(
+        reader: impl io::Read + io::Seek + Send + 'static,
+    ) -> std::result::Result<Codec, Result> {
+        let stream = StreamAsset::from_read_seek(reader).map_err(|_| Result::CouldNotRewind)?;
+        let mut result = Result::Success;
+        Codec::from_ptr(unsafe {
+            sb::C_SkCodec_MakeFromStream(stream.into_ptr() as _, result.native_mut())
+        })
+        .ok_or(result)
+    }
+
+    /// The minimum number of bytes that must be available to create a codec from a stream.
+    pub fn min_buffered_bytes_needed() -> usize {
+        unsafe { sb::C_SkCodec_MinBufferedBytesNeeded() }
+    }
+
+    /// The [`ImageInfo`] of the encoded image.
+    pub fn info(&self) -> ImageInfo {
+        ImageInfo::construct(|ii| unsafe { sb::C_SkCodec_getInfo(self.native(), ii) })
+    }
+
+    pub fn dimensions(&self) -> ISize {
+        ISize::from_native(unsafe { sb::C_SkCodec_dimensions(self.native()) })
+    }
+
+    pub fn bounds(&self) -> IRect {
+        IRect::from_size(self.dimensions())
+    }
+
+    pub fn color_type(&self) -> ColorType {
+        self.info().color_type()
+    }
+
+    pub fn alpha_type(&self) -> AlphaType {
+        self.info().alpha_type()
+    }
+
+    /// The color space derived from the ICC profile embedded in the image, if there is one.
+    pub fn color_space(&self) -> Option<ColorSpace> {
+        self.info().color_space()
+    }
+
+    /// The raw bytes of the ICC profile embedded in the image.
+    pub fn icc_profile(&self) -> Option<&[u8]> {
+        let mut size = 0;
+        let ptr = unsafe { sb::C_SkCodec_getICCProfile(self.native(), &mut size) };
+        if !ptr.is_null() {
+            Some(unsafe { slice::from_raw_parts(ptr, size) })
+        } else {
+            None
+        }
+    }
+
+    pub fn origin(&self) -> EncodedOrigin {
+        EncodedOrigin::from_native(unsafe { sb::C_SkCodec_getOrigin(self.native()) })
+    }
+
+    /// The dimensions of the image after its [`EncodedOrigin`] is applied.
+    pub fn upright_dimensions(&self) -> ISize {
+        self.origin().upright_dimensions(self.dimensions())
+    }
+
+    pub fn encoded_format(&self) -> EncodedImageFormat {
+        EncodedImageFormat::from_native(unsafe { sb::C_SkCodec_getEncodedFormat(self.native()) })
+    }
+
+    pub fn scaled_dimensions(&self, desired_scale: f32) -> ISize {
+        ISize::from_native(unsafe {
+            sb::C_SkCodec_getScaledDimensions(self.native(), desired_scale)
+        })
+    }
+
+    /// Returns a subset that is supported by the codec and is as close as possible to
+    /// `desired_subset`.
+    pub fn valid_subset(&self, desired_subset: impl AsRef<IRect>) -> Option<IRect> {
+        let mut subset = *desired_subset.as_ref();
+        unsafe { sb::C_SkCodec_getValidSubset(self.native(), subset.native_mut()) }
+            .if_true_some(subset)
+    }
+
+    /// Decodes the image into `pixels`, which must be large enough to hold `info.height()`
+    /// rows of `row_bytes` each.
+    #[must_use]
+    pub fn get_pixels(
+        &mut self,
+        info: &ImageInfo,
+        pixels: &mut [u8],
+        row_bytes: usize,
+        options: Option<&Options>,
+    ) -> Result {
+        assert!(info.valid_row_bytes(row_bytes));
+        assert!(pixels.len() >= info.compute_byte_size(row_bytes));
+        let options = options.map(|o| o.to_native());
+        Result::from_native(unsafe {
+            sb::C_SkCodec_getPixels(
+                self.native_mut(),
+                info.native(),
+                pixels.as_mut_ptr() as _,
+                row_bytes,
+                options.as_ptr_or_null(),
+            )
+        })
+    }
+
+    /// Decodes the image into a raster image with the N32 color type and premultiplied alpha.
+    ///
+    /// If `upright` is `true`, the [`EncodedOrigin`] of the image is applied, so that photos
+    /// that were taken with a rotated camera are not shown sideways.
+    pub fn decode_image(&mut self, upright: bool) -> std::result::Result<Image, Result> {
+        let info = ImageInfo::new_n32_premul(self.dimensions(), self.color_space());
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        match self.get_pixels(&info, &mut pixels, row_bytes, None) {
+            Result::Success | Result::IncompleteInput => {}
+            error => return Err(error),
+        }
+        let image = Image::from_raster_data(&info, Data::new_copy(&pixels), row_bytes)
+            .ok_or(Result::InternalError)?;
+        if upright {
+            self.origin()
+                .apply_to_image(&image)
+                .ok_or(Result::InternalError)
+        } else {
+            Ok(image)
+        }
+    }
+}
+
+impl RefHandle<SkCodec> {
+    /// Returns the number of frames in the image.
+    ///
+    /// May require reading through the stream.
+    pub fn frame_count(&mut self) -> usize {
+        unsafe { sb::C_SkCodec_getFrameCount(self.native_mut()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns information about a single frame.
+    ///
+    /// Returns `None` for single-frame images or if `index` is out of range.
+    pub fn frame_info(&self, index: usize) -> Option<FrameInfo> {
+        let mut info = FrameInfo::default();
+        unsafe {
+            sb::C_SkCodec_getFrameInfo(self.native(), index.try_into().unwrap(), info.native_mut())
+        }
+        .if_true_some(info)
+    }
+
+    /// Returns information about all the frames of the image.
+    pub fn frame_infos(&mut self) -> Vec<FrameInfo> {
+        (0..self.frame_count())
+            .filter_map(|i| self.frame_info(i))
+            .collect()
+    }
+
+    /// Returns the number of times to repeat the animation after showing it once, or
+    /// [`REPETITION_COUNT_INFINITE`].
+    pub fn repetition_count(&mut self) -> i32 {
+        unsafe { sb::C_SkCodec_getRepetitionCount(self.native_mut()) }
+    }
+
+    /// Returns an iterator that decodes all frames in order.
+    ///
+    /// The frames are decoded into `info`, or into the dimensions and color space of the
+    /// encoded image with the N32 color type and premultiplied alpha if `info` is `None`.
+    pub fn frames(&mut self, info: impl Into<Option<ImageInfo>>) -> Frames {
+        let info = info
+            .into()
+            .unwrap_or_else(|| ImageInfo::new_n32_premul(self.dimensions(), self.color_space()));
+        let row_bytes = info.min_row_bytes();
+        let pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        let frame_count = self.frame_count();
+        Frames {
+            codec: self,
+            info,
+            row_bytes,
+            pixels,
+            frame_count,
+            index: 0,
+            previous: None,
+        }
+    }
+}
+
+impl RefHandle<SkCodec> {
+    /// Prepares for an incremental decode into `pixels`.
+    ///
+    /// Not all formats support incremental decoding; [`Result::Unimplemented`] is returned if
+    /// this one does not.
+    ///
+    /// # Safety
+    ///
+    /// `pixels` must point to a buffer that holds `info.height()` rows of `row_bytes` each and
+    /// that stays valid until the decode is complete or a new decode is started.
+    #[must_use]
+    pub unsafe fn start_incremental_decode(
+        &mut self,
+        info: &ImageInfo,
+        pixels: *mut u8,
+        row_bytes: usize,
+        options: Option<&Options>,
+    ) -> Result {
+        assert!(info.valid_row_bytes(row_bytes));
+        let options = options.map(|o| o.to_native());
+        Result::from_native(sb::C_SkCodec_startIncrementalDecode(
+            self.native_mut(),
+            info.native(),
+            pixels as _,
+            row_bytes,
+            options.as_ptr_or_null(),
+        ))
+    }
+
+    /// Decodes as much of the image as possible into the pixels that were passed to
+    /// [`Self::start_incremental_decode()`].
+    ///
+    /// Returns [`Result::IncompleteInput`] and the number of rows that are initialized if
+    /// more data is needed to complete the image.
+    pub fn incremental_decode(&mut self) -> (Result, usize) {
+        let mut rows_decoded = 0;
+        let result = Result::from_native(unsafe {
+            sb::C_SkCodec_incrementalDecode(self.native_mut(), &mut rows_decoded)
+        });
+        (result, rows_decoded.try_into().unwrap_or_default())
+    }
+
+    /// Prepares for a scanline decode with the given `info`.
+    ///
+    /// If `options` specifies a subset, only its left and right edges are supported.
+    #[must_use]
+    pub fn start_scanline_decode(&mut self, info: &ImageInfo, options: Option<&Options>) -> Result {
+        let options = options.map(|o| o.to_native());
+        Result::from_native(unsafe {
+            sb::C_SkCodec_startScanlineDecode(
+                self.native_mut(),
+                info.native(),
+                options.as_ptr_or_null(),
+            )
+        })
+    }
+
+    /// Decodes the next `count_lines` rows into `dst` and returns the number of rows that were
+    /// successfully decoded.
+    ///
+    /// Rows that could not be decoded are filled with a default value.
+    pub fn get_scanlines(&mut self, dst: &mut [u8], count_lines: usize, row_bytes: usize) -> usize {
+        assert!(dst.len() >= count_lines * row_bytes);
+        unsafe {
+            sb::C_SkCodec_getScanlines(
+                self.native_mut(),
+                dst.as_mut_ptr() as _,
+                count_lines.try_into().unwrap(),
+                row_bytes,
+            )
+        }
+        .try_into()
+        .unwrap()
+    }
+
+    /// Skips the next `count_lines` rows.
+    pub fn skip_scanlines(&mut self, count_lines: usize) -> bool {
+        unsafe { sb::C_SkCodec_skipScanlines(self.native_mut(), count_lines.try_into().unwrap()) }
+    }
+
+    pub fn scanline_order(&self) -> ScanlineOrder {
+        ScanlineOrder::from_native(unsafe { sb::C_SkCodec_getScanlineOrder(self.native()) })
+    }
+
+    /// The index of the next row to be decoded, as it is ordered in the encoded image.
+    pub fn next_scanline(&self) -> usize {
+        unsafe { sb::C_SkCodec_nextScanline(self.native()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Returns the row of the output image that the row `input_scanline` of the encoded image
+    /// belongs to.
+    pub fn output_scanline(&self, input_scanline: usize) -> usize {
+        unsafe { sb::C_SkCodec_outputScanline(self.native(), input_scanline.try_into().unwrap()) }
+            .try_into()
+            .unwrap()
+    }
+
+    /// Starts a scanline decode and returns an iterator over the decoded rows.
+    ///
+    /// The rows are decoded into `info`, or into the dimensions and color space of the encoded
+    /// image with the N32 color type and premultiplied alpha if `info` is `None`.
+    pub fn scanlines(
+        &mut self,
+        info: impl Into<Option<ImageInfo>>,
+    ) -> std::result::Result<Scanlines, Result> {
+        let info = info
+            .into()
+            .unwrap_or_else(|| ImageInfo::new_n32_premul(self.dimensions(), self.color_space()));
+        match self.start_scanline_decode(&info, None) {
+            Result::Success => {}
+            error => return Err(error),
+        }
+        let row_bytes = info.min_row_bytes();
+        let remaining = info.height().try_into().unwrap();
+        Ok(Scanlines {
+            codec: self,
+            info,
+            row_bytes,
+            remaining,
+        })
+    }
+}
+
+/// A row returned by the [`Scanlines`] iterator.
+pub struct Scanline {
+    /// The row of the output image the pixels belong to.
+    pub row: usize,
+    pub pixels: Vec<u8>,
+}
+
+/// Decodes the rows of an image one by one, so that the complete image never needs to be held
+/// in memory.
+pub struct Scanlines<'a> {
+    codec: &'a mut Codec,
+    info: ImageInfo,
+    row_bytes: usize,
+    remaining: usize,
+}
+
+impl Scanlines<'_> {
+    /// The [`ImageInfo`] the rows are decoded into.
+    pub fn info(&self) -> &ImageInfo {
+        &self.info
+    }
+
+    /// The number of bytes of a single row.
+    pub fn row_bytes(&self) -> usize {
+        self.row_bytes
+    }
+
+    /// Decodes the next row into `dst` without allocating, and returns the row of the output
+    /// image it belongs to.
+    pub fn next_into(&mut self, dst: &mut [u8]) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let row = self.codec.output_scanline(self.codec.next_scanline());
+        if self.codec.get_scanlines(dst, 1, self.row_bytes) == 0 {
+            self.remaining = 0;
+            return None;
+        }
+        self.remaining -= 1;
+        Some(row)
+    }
+
+    /// Skips the next `count` rows.
+    pub fn skip(&mut self, count: usize) -> bool {
+        let count = count.min(self.remaining);
+        let skipped = self.codec.skip_scanlines(count);
+        self.remaining = if skipped { self.remaining - count } else { 0 };
+        skipped
+    }
+}
+
+impl Iterator for Scanlines<'_> {
+    type Item = Scanline;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut pixels = vec![0u8; self.row_bytes];
+        self.next_into(&mut pixels)
+            .map(|row| Scanline { row, pixels })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+/// Decodes an image while its encoded bytes are still arriving, for example from a socket.
+///
+/// The image is decoded into the dimensions and color space of the encoded image with the N32
+/// color type and premultiplied alpha. Incremental decoding is supported for PNG and GIF images.
+pub struct IncrementalDecoder {
+    input: Arc<Mutex<Vec<u8>>>,
+    codec: Option<Codec>,
+    info: Option<ImageInfo>,
+    row_bytes: usize,
+    pixels: Vec<u8>,
+    rows_decoded: usize,
+    result: Result,
+}
+
+impl Default for IncrementalDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalDecoder {
+    pub fn new() -> IncrementalDecoder {
+        IncrementalDecoder {
+            input: Arc::new(Mutex::new(Vec::new())),
+            codec: None,
+            info: None,
+            row_bytes: 0,
+            pixels: Vec::new(),
+            rows_decoded: 0,
+            result: Result::IncompleteInput,
+        }
+    }
+
+    /// Appends `bytes` to the encoded image and decodes as much of it as possible.
+    ///
+    /// Returns [`Result::IncompleteInput`] as long as more bytes are needed, [`Result::Success`]
+    /// when the image is complete, and any other result if decoding failed.
+    pub fn feed(&mut self, bytes: &[u8]) -> Result {
+        if self.result != Result::IncompleteInput {
+            return self.result;
+        }
+        self.input.lock().unwrap().extend_from_slice(bytes);
+        self.result = self.decode();
+        self.result
+    }
+
+    fn decode(&mut self) -> Result {
+        if self.codec.is_none() {
+            if self.input.lock().unwrap().len() < Codec::min_buffered_bytes_needed() {
+                return Result::IncompleteInput;
+            }
+            let reader = InputReader {
+                input: self.input.clone(),
+                position: 0,
+            };
+            let mut codec = match Codec::from_read(reader) {
+                Ok(codec) => codec,
+                Err(error) => return error,
+            };
+            let info = ImageInfo::new_n32_premul(codec.dimensions(), codec.color_space());
+            let row_bytes = info.min_row_bytes();
+            self.pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+            match unsafe {
+                codec.start_incremental_decode(&info, self.pixels.as_mut_ptr(), row_bytes, None)
+            } {
+                Result::Success => {}
+                error => return error,
+            }
+            self.codec = Some(codec);
+            self.info = Some(info);
+            self.row_bytes = row_bytes;
+        }
+
+        let (result, rows_decoded) = self.codec.as_mut().unwrap().incremental_decode();
+        self.rows_decoded = match result {
+            Result::Success => self.info.as_ref().unwrap().height().try_into().unwrap(),
+            _ => rows_decoded,
+        };
+        result
+    }
+
+    /// The [`ImageInfo`] of the decoded image, available as soon as the header was received.
+    pub fn info(&self) -> Option<&ImageInfo> {
+        self.info.as_ref()
+    }
+
+    /// The number of rows that were decoded so far.
+    pub fn rows_decoded(&self) -> usize {
+        self.rows_decoded
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.result.is_success()
+    }
+
+    /// The pixels decoded so far. Rows that were not decoded yet are transparent.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns a copy of the image decoded so far.
+    pub fn image(&self) -> Option<Image> {
+        let info = self.info.as_ref()?;
+        Image::from_raster_data(info, Data::new_copy(&self.pixels), self.row_bytes)
+    }
+}
+
+// Reads the bytes that were fed to an IncrementalDecoder so far.
+struct InputReader {
+    input: Arc<Mutex<Vec<u8>>>,
+    position: usize,
+}
+
+impl io::Read for InputReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let input = self.input.lock().unwrap();
+        let available = &input[self.position..];
+        let read = available.len().min(buf.len());
+        buf[..read].copy_from_slice(&available[..read]);
+        self.position += read;
+        Ok(read)
+    }
+}
+
+/// A decoded frame returned by the [`Frames`] iterator.
+pub struct Frame {
+    pub index: usize,
+    pub info: FrameInfo,
+    pub image: Image,
+}
+
+/// Decodes the frames of an image one by one.
+///
+/// Each frame is decoded on top of the previous one if it depends on it, so that the codec
+/// does not need to decode the required frames again.
+pub struct Frames<'a> {
+    codec: &'a mut Codec,
+    info: ImageInfo,
+    row_bytes: usize,
+    pixels: Vec<u8>,
+    frame_count: usize,
+    index: usize,
+    // The frame the pixel buffer currently contains.
+    previous: Option<FrameInfo>,
+}
+
+impl Iterator for Frames<'_> {
+    type Item = std::result::Result<Frame, Result>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.frame_count {
+            return None;
+        }
+        let index = self.index;
+        self.index += 1;
+
+        let frame_info = self.codec.frame_info(index).unwrap_or_default();
+
+        // The frame in the buffer can only be reused if the current frame depends on it and
+        // it does not need to be restored.
+        let prior_frame = match self.previous {
+            Some(previous)
+                if frame_info.required_frame != NO_FRAME
+                    && previous.disposal_method
+                        != codec_animation::DisposalMethod::RestorePrevious =>
+            {
+                Some(index - 1)
+            }
+            _ => None,
+        };
+
+        let options = Options {
+            frame_index: index,
+            prior_frame,
+            ..Options::default()
+        };
+
+        match self
+            .codec
+            .get_pixels(&self.info, &mut self.pixels, self.row_bytes, Some(&options))
+        {
+            Result::Success | Result::IncompleteInput => {}
+            error => {
+                self.index = self.frame_count;
+                return Some(Err(error));
+            }
+        }
+        self.previous = Some(frame_info);
+
+        let image =
+            Image::from_raster_data(&self.info, Data::new_copy(&self.pixels), self.row_bytes)
+                .unwrap();
+
+        Some(Ok(Frame {
+            index,
+            info: frame_info,
+            image,
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.frame_count - self.index;
+        (0, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{Codec, IncrementalDecoder, Result, REPETITION_COUNT_INFINITE};
+    use crate::{
+        AlphaType, Color, EncodedImageFormat, EncodedOrigin, ISize, ImageInfo, Pixmap, Surface,
+    };
+    use std::io;
+
+    fn encoded_png() -> crate::Data {
+        let mut surface = Surface::new_raster_n32_premul((16, 8)).unwrap();
+        surface.canvas().clear(Color::RED);
+        surface
+            .image_snapshot()
+            .encode_to_data(EncodedImageFormat::PNG)
+            .unwrap()
+    }
+
+    #[test]
+    fn codec_reports_info_without_decoding() {
+        let data = encoded_png();
+        let codec = Codec::from_data(data).unwrap();
+        assert_eq!(
+            (16, 8),
+            (codec.dimensions().width, codec.dimensions().height)
+        );
+        assert_eq!(EncodedImageFormat::PNG, codec.encoded_format());
+        assert_eq!(EncodedOrigin::TopLeft, codec.origin());
+    }
+
+    #[test]
+    fn codec_decodes_into_pixels() {
+        let data = encoded_png();
+        let mut codec = Codec::from_bytes(&data).unwrap();
+        let info = ImageInfo::new_n32(codec.dimensions(), AlphaType::Premul, None);
+        let row_bytes = info.min_row_bytes();
+        let mut pixels = vec![0u8; info.compute_byte_size(row_bytes)];
+        assert_eq!(
+            Result::Success,
+            codec.get_pixels(&info, &mut pixels, row_bytes, None)
+        );
+        let pixmap = Pixmap::new(&info, &pixels, row_bytes);
+        assert_eq!(Color::RED, pixmap.get_color((3, 3)));
+    }
+
+    // A 4x4 GIF with three frames (red, green, blue) that are shown for 100, 200 and 300ms and
+    // loop forever.
+    pub(crate) const ANIMATED_GIF: &[u8] = &[
+        0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x04, 0x00, 0x04, 0x00, 0xf1, 0x00, 0x00, 0xff, 0x00,
+        0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0x21, 0xff, 0x0b, 0x4e, 0x45,
+        0x54, 0x53, 0x43, 0x41, 0x50, 0x45, 0x32, 0x2e, 0x30, 0x03, 0x01, 0x00, 0x00, 0x00, 0x21,
+        0xf9, 0x04, 0x04, 0x0a, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x04,
+        0x00, 0x00, 0x02, 0x04, 0x84, 0x8f, 0x09, 0x05, 0x00, 0x21, 0xf9, 0x04, 0x04, 0x14, 0x00,
+        0x00, 0x00, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x04, 0x00, 0x00, 0x02, 0x04, 0x8c,
+        0x8f, 0x19, 0x05, 0x00, 0x21, 0xf9, 0x04, 0x04, 0x1e, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00,
+        0x00, 0x00, 0x04, 0x00, 0x04, 0x00, 0x00, 0x02, 0x04, 0x94, 0x8f, 0x29, 0x05, 0x00, 0x3b,
+    ];
+
+    #[test]
+    fn codec_reports_frames() {
+        let mut codec = Codec::from_bytes(ANIMATED_GIF).unwrap();
+        assert_eq!(EncodedImageFormat::GIF, codec.encoded_format());
+        assert_eq!(3, codec.frame_count());
+        assert_eq!(REPETITION_COUNT_INFINITE, codec.repetition_count());
+        let durations: Vec<i32> = codec.frame_infos().iter().map(|f| f.duration).collect();
+        assert_eq!(vec![100, 200, 300], durations);
+        assert!(codec.frame_info(3).is_none());
+    }
+
+    #[test]
+    fn codec_decodes_frames_in_order() {
+        let mut codec = Codec::from_bytes(ANIMATED_GIF).unwrap();
+        let colors: Vec<Color> = codec
+            .frames(None)
+            .map(|frame| {
+                let frame = frame.unwrap();
+                let pixmap = frame.image.peek_pixels().unwrap();
+                pixmap.get_color((2, 2))
+            })
+            .collect();
+        assert_eq!(vec![Color::RED, Color::GREEN, Color::BLUE], colors);
+    }
+
+    #[test]
+    fn codec_decodes_scanlines_from_reader() {
+        let data = encoded_png();
+        let mut codec = Codec::from_read(io::Cursor::new(data.as_bytes().to_vec())).unwrap();
+        assert_eq!(EncodedImageFormat::PNG, codec.encoded_format());
+        let rows: Vec<usize> = codec
+            .scanlines(None)
+            .unwrap()
+            .map(|scanline| {
+                assert_eq!(16 * 4, scanline.pixels.len());
+                scanline.row
+            })
+            .collect();
+        assert_eq!((0..8).collect::<Vec<_>>(), rows);
+    }
+
+    #[test]
+    fn incremental_decoder_decodes_partial_input() {
+        let data = encoded_png();
+        let mut decoder = IncrementalDecoder::new();
+        let results: Vec<Result> = data.chunks(16).map(|chunk| decoder.feed(chunk)).collect();
+        assert_eq!(Result::IncompleteInput, results[0]);
+        assert_eq!(Result::Success, *results.last().unwrap());
+        assert!(decoder.is_complete());
+        assert_eq!(8, decoder.rows_decoded());
+        let image = decoder.image().unwrap();
+        assert_eq!(Color::RED, image.peek_pixels().unwrap().get_color((15, 7)));
+    }
+
+    #[test]
+    fn codec_from_read_reports_incomplete_input() {
+        let data = encoded_png();
+        let header = data.as_bytes()[..40].to_vec();
+        assert_eq!(
+            Some(Result::IncompleteInput),
+            Codec::from_read(io::Cursor::new(header)).err()
+        );
+    }
+
+    #[test]
+    fn codec_from_stream_decodes_repeatedly() {
+        let data = encoded_png();
+        let mut codec = Codec::from_stream(io::Cursor::new(data.as_bytes().to_vec())).unwrap();
+        for _ in 0..2 {
+            let image = codec.decode_image(false).unwrap();
+            assert_eq!(Color::RED, image.peek_pixels().unwrap().get_color((15, 7)));
+        }
+    }
+
+    // Inserts an Exif segment that contains only the orientation tag after the SOI marker of a
+    // JPEG image.
+    pub(crate) fn with_exif_orientation(jpeg: &[u8], orientation: u8) -> Vec<u8> {
+        let exif = [
+            0xff,
+            0xe1,
+            0x00,
+            0x22,
+            b'E',
+            b'x',
+            b'i',
+            b'f',
+            0x00,
+            0x00,
+            b'M',
+            b'M',
+            0x00,
+            0x2a,
+            0x00,
+            0x00,
+            0x00,
+            0x08,
+            0x00,
+            0x01,
+            0x01,
+            0x12,
+            0x00,
+            0x03,
+            0x00,
+            0x00,
+            0x00,
+            0x01,
+            0x00,
+            orientation,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+        ];
+        [&jpeg[..2], &exif, &jpeg[2..]].concat()
+    }
+
+    // A 16x8 JPEG image with a red left and a blue right half.
+    pub(crate) fn encoded_jpeg_halves() -> crate::Data {
+        let mut surface = Surface::new_raster_n32_premul((16, 8)).unwrap();
+        let canvas = surface.canvas();
+        let mut paint = crate::Paint::default();
+        paint.set_color(Color::RED);
+        canvas.clear(Color::BLUE);
+        canvas.draw_rect(crate::Rect::from_wh(8.0, 8.0), &paint);
+        surface
+            .image_snapshot()
+            .encode_to_data(EncodedImageFormat::JPEG)
+            .unwrap()
+    }
+
+    #[test]
+    fn codec_decodes_upright_image() {
+        let jpeg = with_exif_orientation(&encoded_jpeg_halves(), 6);
+        let mut codec = Codec::from_bytes(&jpeg).unwrap();
+        assert_eq!(EncodedOrigin::RightTop, codec.origin());
+        assert_eq!(ISize::new(8, 16), codec.upright_dimensions());
+
+        let image = codec.decode_image(false).unwrap();
+        assert_eq!(ISize::new(16, 8), image.dimensions());
+
+        let mut codec = Codec::from_bytes(&jpeg).unwrap();
+        let image = codec.decode_image(true).unwrap();
+        assert_eq!(ISize::new(8, 16), image.dimensions());
+        let pixmap = image.peek_pixels().unwrap();
+        // the left half is rotated to the top.
+        assert!(pixmap.get_color((4, 2)).r() > 0xc0);
+        assert!(pixmap.get_color((4, 13)).b() > 0xc0);
+    }
+
+    #[test]
+    fn codec_rejects_garbage() {
+        assert!(Codec::from_bytes(&[0u8, 1, 2, 3]).is_none());
+    }
+}
@@ -0,0 +1,25 @@
+pub mod codec_animation {
+    use crate::prelude::*;
+    use skia_bindings::SkCodecAnimation_DisposalMethod;
+
+    /// This specifies how the next frame is based on this frame.
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[repr(i32)]
+    pub enum DisposalMethod {
+        Keep = SkCodecAnimation_DisposalMethod::kKeep as _,
+        RestoreBGColor = SkCodecAnimation_DisposalMethod::kRestoreBGColor as _,
+        RestorePrevious = SkCodecAnimation_DisposalMethod::kRestorePrevious as _,
+    }
+
+    impl NativeTransmutable<SkCodecAnimation_DisposalMethod> for DisposalMethod {}
+    #[test]
+    fn test_disposal_method_layout() {
+        DisposalMethod::test_layout()
+    }
+
+    impl Default for DisposalMethod {
+        fn default() -> Self {
+            DisposalMethod::Keep
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::prelude::NativeTransmutable;
-use crate::{ISize, Matrix};
+use crate::{ISize, Image, Matrix, Surface};
 use skia_bindings as sb;
 use skia_bindings::SkEncodedOrigin;
 
@@ -42,4 +42,55 @@ impl EncodedOrigin {
         };
         m
     }
+
+    /// Returns `true` if the image needs to be rotated by 90 or 270 degrees, which swaps its
+    /// width and height.
+    pub fn swaps_width_height(self) -> bool {
+        match self {
+            EncodedOrigin::LeftTop
+            | EncodedOrigin::RightTop
+            | EncodedOrigin::RightBottom
+            | EncodedOrigin::LeftBottom => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the dimensions an encoded image of `size` has after the origin is applied.
+    pub fn upright_dimensions(self, size: impl Into<ISize>) -> ISize {
+        let size = size.into();
+        if self.swaps_width_height() {
+            ISize::new(size.height, size.width)
+        } else {
+            size
+        }
+    }
+
+    /// Draws `image`, which has the orientation of the encoded data, into a new raster image that
+    /// is upright.
+    pub fn apply_to_image(self, image: &Image) -> Option<Image> {
+        if self == EncodedOrigin::TopLeft {
+            return Some(image.clone());
+        }
+        let info = image
+            .image_info()
+            .with_dimensions(self.upright_dimensions(image.dimensions()));
+        let mut surface = Surface::new_raster(&info, None, None)?;
+        surface
+            .canvas()
+            .concat(&self.to_matrix(image.dimensions()))
+            .draw_image(image, (0, 0), None);
+        Some(surface.image_snapshot())
+    }
+}
+
+#[test]
+fn upright_dimensions_swap_for_rotations() {
+    assert_eq!(
+        ISize::new(16, 8),
+        EncodedOrigin::BottomRight.upright_dimensions((16, 8))
+    );
+    assert_eq!(
+        ISize::new(8, 16),
+        EncodedOrigin::RightTop.upright_dimensions((16, 8))
+    );
 }
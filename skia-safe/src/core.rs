@@ -80,8 +80,8 @@ pub use drawable::Drawable;
 mod encoded_image_format;
 pub use encoded_image_format::*;
 
-// unsupported, because it's used in experimental APIs only.
-// mod executor;
+pub mod executor;
+pub use executor::Executor;
 
 mod filter_quality;
 pub use filter_quality::*;
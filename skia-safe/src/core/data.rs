@@ -1,8 +1,12 @@
+use crate::interop::Stream;
 use crate::prelude::*;
 use skia_bindings as sb;
 use skia_bindings::SkData;
 use std::ffi::{CStr, CString};
+use std::io;
 use std::ops::Deref;
+use std::os::raw::c_void;
+use std::path::Path;
 use std::slice;
 
 pub type Data = RCHandle<SkData>;
@@ -72,6 +76,50 @@ impl RCHandle<SkData> {
             .unwrap()
     }
 
+    /// Constructs Data that refers to `owned` without copying its bytes.
+    ///
+    /// `owned` is dropped when the last reference to the Data is released, which may happen on
+    /// another thread.
+    pub fn from_owned<T: AsRef<[u8]> + Send + 'static>(owned: T) -> Self {
+        let owned = Box::new(owned);
+        let bytes = (*owned).as_ref();
+        let (ptr, len) = (bytes.as_ptr(), bytes.len());
+        Data::from_ptr(unsafe {
+            sb::C_SkData_MakeWithProc(
+                ptr as _,
+                len,
+                Some(release_owned::<T>),
+                Box::into_raw(owned) as _,
+            )
+        })
+        .unwrap()
+    }
+
+    /// Constructs Data from a vector without copying it.
+    pub fn from_vec(vec: Vec<u8>) -> Self {
+        Self::from_owned(vec)
+    }
+
+    /// Constructs Data from a boxed slice without copying it.
+    pub fn from_boxed(boxed: Box<[u8]>) -> Self {
+        Self::from_owned(boxed)
+    }
+
+    /// Constructs Data that refers to static bytes without copying them.
+    pub fn from_static(bytes: &'static [u8]) -> Self {
+        Data::from_ptr(unsafe { sb::C_SkData_MakeWithoutCopy(bytes.as_ptr() as _, bytes.len()) })
+            .unwrap()
+    }
+
+    /// Constructs Data from the contents of a file, which is memory-mapped if possible.
+    ///
+    /// Returns `None` if the file can not be opened or read, or if the path is not valid UTF-8
+    /// on platforms other than Unix.
+    pub fn from_file(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path_to_cstring(path.as_ref())?;
+        Data::from_ptr(unsafe { sb::C_SkData_MakeFromFileName(path.as_ptr()) })
+    }
+
     pub unsafe fn new_uninitialized(length: usize) -> Data {
         Data::from_ptr(sb::C_SkData_MakeUninitialized(length)).unwrap()
     }
@@ -95,15 +143,35 @@ impl RCHandle<SkData> {
         Data::from_ptr(unsafe { sb::C_SkData_MakeWithCString(cstr.as_ptr()) }).unwrap()
     }
 
-    // TODO: MakeFromFileName (not sure if we need that)
-    // TODO: MakeFromFile (not sure if we need that)
-    // TODO: MakeFromStream
+    /// Reads `size` bytes from `reader`.
+    ///
+    /// Returns `None` if the reader provides fewer bytes.
+    pub fn from_stream(reader: impl io::Read + Send + 'static, size: usize) -> Option<Data> {
+        let mut stream = Stream::from_read(reader);
+        Data::from_ptr(unsafe { sb::C_SkData_MakeFromStream(stream.native_mut(), size) })
+    }
 
     pub fn new_empty() -> Self {
         Data::from_ptr(unsafe { sb::C_SkData_MakeEmpty() }).unwrap()
     }
 }
 
+extern "C" fn release_owned<T>(_ptr: *const c_void, context: *mut c_void) {
+    drop(unsafe { Box::from_raw(context as *mut T) })
+}
+
+#[cfg(unix)]
+fn path_to_cstring(path: &Path) -> Option<CString> {
+    use std::os::unix::ffi::OsStrExt;
+    CString::new(path.as_os_str().as_bytes()).ok()
+}
+
+// Skia expects UTF-8 paths on Windows.
+#[cfg(not(unix))]
+fn path_to_cstring(path: &Path) -> Option<CString> {
+    CString::new(path.to_str()?).ok()
+}
+
 #[cfg(test)]
 impl RefCount for SkData {
     fn ref_cnt(&self) -> usize {
@@ -118,3 +186,66 @@ fn data_supports_equals() {
     let d2 = Data::new_copy(x);
     assert!(d1 == d2)
 }
+
+#[test]
+fn data_from_stream_reads_size_bytes() {
+    let reader = io::Cursor::new(vec![1u8, 2, 3, 4]);
+    assert_eq!(
+        &[1u8, 2, 3],
+        Data::from_stream(reader, 3).unwrap().as_bytes()
+    );
+    assert!(Data::from_stream(io::Cursor::new(vec![1u8]), 2).is_none());
+}
+
+#[test]
+fn data_from_owned_refers_to_the_owned_bytes() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct Owned(Vec<u8>, Arc<AtomicBool>);
+
+    impl AsRef<[u8]> for Owned {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl Drop for Owned {
+        fn drop(&mut self) {
+            self.1.store(true, Ordering::SeqCst)
+        }
+    }
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let vec = vec![1u8, 2, 3];
+    let ptr = vec.as_ptr();
+    let data = Data::from_owned(Owned(vec, dropped.clone()));
+    assert_eq!(ptr, data.as_bytes().as_ptr());
+    let clone = data.clone();
+    drop(data);
+    assert!(!dropped.load(Ordering::SeqCst));
+    assert_eq!(&[1u8, 2, 3], clone.as_bytes());
+    drop(clone);
+    assert!(dropped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn data_from_static_and_vec() {
+    static BYTES: &[u8] = b"static";
+    assert_eq!(BYTES.as_ptr(), Data::from_static(BYTES).as_bytes().as_ptr());
+    assert_eq!(b"vec", Data::from_vec(b"vec".to_vec()).as_bytes());
+    assert_eq!(
+        b"boxed",
+        Data::from_boxed(b"boxed".to_vec().into_boxed_slice()).as_bytes()
+    );
+}
+
+#[test]
+fn data_from_file() {
+    let path = std::env::temp_dir().join("skia-safe-data-from-file.bin");
+    std::fs::write(&path, b"file contents").unwrap();
+    let data = Data::from_file(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(b"file contents", data.as_bytes());
+    assert!(Data::from_file(std::env::temp_dir().join("skia-safe-missing-file.bin")).is_none());
+}
@@ -1,14 +1,18 @@
-use crate::interop::DynamicMemoryWStream;
+use crate::interop::{DynamicMemoryWStream, WriterStream};
 use crate::prelude::*;
-use crate::{Canvas, Data, Rect, Size};
+use crate::{Canvas, Data, Executor, Rect, Size};
 use skia_bindings::{SkDocument, SkRefCntBase};
+use std::io;
 use std::pin::Pin;
 
-pub struct Document<State = state::Open> {
+/// A document that is written to memory, or to a writer if `Stream` is a [`WriterStream`].
+pub struct Document<State = state::Open, Stream = DynamicMemoryWStream> {
     // note: order matters here, first the document must be
     // dropped _and then_ the stream.
     document: RCHandle<SkDocument>,
-    stream: Pin<Box<DynamicMemoryWStream>>,
+    stream: Pin<Box<Stream>>,
+    // the executor the document uses, which must outlive the document, too.
+    executor: Option<Executor>,
 
     state: State,
 }
@@ -32,21 +36,23 @@ pub mod state {
     }
 }
 
-impl<S> Document<S> {
+impl<State, Stream> Document<State, Stream> {
     pub fn abort(mut self) {
         unsafe { self.document.native_mut().abort() }
         drop(self)
     }
 }
 
-impl Document {
+impl<Stream> Document<state::Open, Stream> {
     pub(crate) fn new(
-        stream: Pin<Box<DynamicMemoryWStream>>,
+        stream: Pin<Box<Stream>>,
         document: RCHandle<SkDocument>,
+        executor: Option<Executor>,
     ) -> Self {
         Document {
             document,
             stream,
+            executor,
             state: state::Open { pages: 0 },
         }
     }
@@ -62,7 +68,7 @@ impl Document {
         mut self,
         size: impl Into<Size>,
         content: Option<&Rect>,
-    ) -> Document<state::OnPage> {
+    ) -> Document<state::OnPage, Stream> {
         let size = size.into();
         let canvas = unsafe {
             self.document.native_mut().beginPage(
@@ -75,13 +81,16 @@ impl Document {
         Document {
             stream: self.stream,
             document: self.document,
+            executor: self.executor,
             state: state::OnPage {
                 canvas,
                 page: self.state.pages + 1,
             },
         } as _
     }
+}
 
+impl Document {
     /// Close the document and return the encoded representation.
     /// This function consumes and drops the document.
     pub fn close(mut self) -> Data {
@@ -92,7 +101,28 @@ impl Document {
     }
 }
 
-impl Document<state::OnPage> {
+impl<W: io::Write> Document<state::Open, WriterStream<W>> {
+    /// Close the document, flush the writer and return it.
+    /// This function consumes and drops the document.
+    ///
+    /// Returns the first I/O error the writer returned while the document was written.
+    pub fn close(self) -> io::Result<W> {
+        let Document {
+            mut document,
+            stream,
+            executor,
+            ..
+        } = self;
+        unsafe {
+            document.native_mut().close();
+        }
+        drop(document);
+        drop(executor);
+        Pin::into_inner(stream).into_writer()
+    }
+}
+
+impl<Stream> Document<state::OnPage, Stream> {
     /// The current page we are currently drawing on.
     pub fn page(&self) -> usize {
         self.state.page
@@ -106,7 +136,7 @@ impl Document<state::OnPage> {
     /// Ends the page.
     /// This function consumes the document and returns a new open document that
     /// contains the pages drawn so far.
-    pub fn end_page(mut self) -> Document {
+    pub fn end_page(mut self) -> Document<state::Open, Stream> {
         unsafe {
             self.document.native_mut().endPage();
         }
@@ -114,6 +144,7 @@ impl Document<state::OnPage> {
         Document {
             stream: self.stream,
             document: self.document,
+            executor: self.executor,
             state: state::Open {
                 pages: self.state.page,
             },
@@ -0,0 +1,105 @@
+use crate::prelude::*;
+use skia_bindings as sb;
+use skia_bindings::{RustExecutor_Param, SkExecutor};
+use std::ffi::c_void;
+use std::fmt;
+use std::sync::Arc;
+
+/// Runs work items that Skia distributes, for example the jobs a PDF document processes in
+/// parallel.
+///
+/// Executors are cheap to clone, all clones refer to the same native executor.
+#[derive(Clone)]
+pub struct Executor(Arc<RefHandle<SkExecutor>>);
+
+unsafe impl Send for Executor {}
+unsafe impl Sync for Executor {}
+
+impl NativeDrop for SkExecutor {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkExecutor_delete(self) }
+    }
+}
+
+impl fmt::Debug for Executor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Executor")
+            .field(&(self.native_ptr() as *const SkExecutor))
+            .finish()
+    }
+}
+
+/// A work item that Skia added to an executor created with [`Executor::from_fn()`].
+///
+/// Skia may wait for its work items to complete, so dropping a work item without running it
+/// can block the thread that added it forever.
+pub struct Work(*mut c_void);
+
+unsafe impl Send for Work {}
+
+impl Drop for Work {
+    fn drop(&mut self) {
+        unsafe { sb::C_RustExecutor_deleteWork(self.0) }
+    }
+}
+
+impl Work {
+    pub fn run(self) {
+        let work = self.0;
+        std::mem::forget(self);
+        unsafe { sb::C_RustExecutor_runWork(work) }
+    }
+}
+
+impl Executor {
+    /// Creates a Skia thread pool that runs the work items in the order they were added.
+    ///
+    /// If `threads` is `None`, one thread per core is used.
+    pub fn new_fifo_thread_pool(threads: impl Into<Option<usize>>) -> Self {
+        let threads = threads.into().unwrap_or_default().try_into().unwrap();
+        Self::from_ptr(unsafe { sb::C_SkExecutor_MakeFIFOThreadPool(threads) })
+    }
+
+    /// Creates a Skia thread pool that runs the most recently added work items first.
+    ///
+    /// If `threads` is `None`, one thread per core is used.
+    pub fn new_lifo_thread_pool(threads: impl Into<Option<usize>>) -> Self {
+        let threads = threads.into().unwrap_or_default().try_into().unwrap();
+        Self::from_ptr(unsafe { sb::C_SkExecutor_MakeLIFOThreadPool(threads) })
+    }
+
+    /// Creates an executor that passes the work items to `spawn`, which may run them on any
+    /// thread, for example on a Rust thread pool.
+    ///
+    /// `spawn` is called from the threads that add work, and must not block until the work was
+    /// run.
+    pub fn from_fn(spawn: impl Fn(Work) + Send + Sync + 'static) -> Self {
+        new_from_fn(spawn)
+    }
+
+    pub(crate) fn native_ptr(&self) -> *mut SkExecutor {
+        self.0.native() as *const SkExecutor as *mut _
+    }
+
+    fn from_ptr(ptr: *mut SkExecutor) -> Self {
+        Executor(Arc::new(RefHandle::from_ptr(ptr).unwrap()))
+    }
+}
+
+fn new_from_fn<F: Fn(Work) + Send + Sync + 'static>(spawn: F) -> Executor {
+    let param = RustExecutor_Param {
+        data: Box::into_raw(Box::new(spawn)) as _,
+        add: Some(executor_add::<F>),
+        drop: Some(executor_drop::<F>),
+    };
+    Executor::from_ptr(unsafe { sb::C_RustExecutor_new(&param) })
+}
+
+extern "C" fn executor_add<F: Fn(Work)>(data: *mut c_void, work: *mut c_void) {
+    let spawn = unsafe { &*(data as *const F) };
+    spawn(Work(work))
+}
+
+extern "C" fn executor_drop<F>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut F) })
+}
@@ -29,6 +29,12 @@ impl NativeDrop for SkFont {
     }
 }
 
+impl NativeClone for SkFont {
+    fn clone(&self) -> Self {
+        construct(|font| unsafe { sb::C_SkFont_CopyConstruct(font, self) })
+    }
+}
+
 impl NativePartialEq for SkFont {
     fn eq(&self, rhs: &Self) -> bool {
         unsafe { sb::C_SkFont_Equals(self, rhs) }
@@ -1,10 +1,11 @@
 use crate::interop;
-use crate::interop::DynamicMemoryWStream;
+use crate::interop::{DynamicMemoryWStream, StreamAsset};
 use crate::prelude::*;
 use crate::{FontStyle, Typeface, Unichar};
 use skia_bindings as sb;
 use skia_bindings::{SkFontMgr, SkFontStyleSet, SkRefCntBase};
 use std::ffi::CString;
+use std::io;
 use std::mem;
 use std::os::raw::c_char;
 
@@ -190,12 +191,40 @@ impl RCHandle<SkFontMgr> {
         })
     }
 
-    // TODO: makeFromStream(.., ttcIndex).
+    /// Creates a typeface that reads the font data from `reader` when needed, instead of
+    /// loading it into memory.
+    ///
+    /// Returns `None` if the reader can not be seeked or does not contain a supported font.
+    pub fn new_from_stream(
+        &self,
+        reader: impl io::Read + io::Seek + Send + 'static,
+        ttc_index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        let stream = StreamAsset::from_read_seek(reader).ok()?;
+        Typeface::from_ptr(unsafe {
+            sb::C_SkFontMgr_makeFromStream(
+                self.native(),
+                stream.into_ptr(),
+                ttc_index.into().unwrap_or_default().try_into().unwrap(),
+            )
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::FontMgr;
+    use crate::core::typeface::tests::font_file;
+    use crate::{FontMgr, Typeface};
+    use std::io;
+
+    #[test]
+    fn new_from_stream_loads_a_font_from_a_reader() {
+        let typeface = Typeface::default();
+        let reader = io::Cursor::new(font_file(&typeface));
+        let loaded = FontMgr::new().new_from_stream(reader, None).unwrap();
+        assert_eq!(typeface.count_glyphs(), loaded.count_glyphs());
+        assert_eq!(typeface.family_name(), loaded.family_name());
+    }
 
     #[test]
     #[serial_test_derive::serial]
@@ -1,5 +1,8 @@
-use skia_bindings::SkGraphics;
+use crate::{Data, ImageGenerator};
+use skia_bindings as sb;
+use skia_bindings::{SkData, SkGraphics, SkImageGenerator};
 use std::ffi::CString;
+use std::sync::{Arc, RwLock};
 
 pub fn init() {
     unsafe { SkGraphics::Init() };
@@ -88,4 +91,136 @@ pub fn set_flags(flags: impl AsRef<str>) {
     unsafe { SkGraphics::SetFlags(c_str.as_ptr()) }
 }
 
-// TODO: ImageGeneratorFromEncodedDataFactory
+type ImageGeneratorFromEncodedDataFactory =
+    Arc<dyn Fn(Data) -> Option<ImageGenerator> + Send + Sync>;
+
+lazy_static! {
+    static ref IMAGE_GENERATOR_FACTORY: RwLock<Option<ImageGeneratorFromEncodedDataFactory>> =
+        RwLock::new(None);
+}
+
+/// Sets the factory Skia uses first to create image generators from encoded data, for example
+/// in `Image::from_encoded()`, `Picture::from_data()` and when deserializing pictures.
+///
+/// If the factory returns `None`, Skia decodes the data with its own codecs, so the factory
+/// only needs to handle the formats Skia does not support. The factory can be called from
+/// any thread, and may replace or reset itself.
+pub fn set_image_generator_from_encoded_data_factory(
+    factory: impl Fn(Data) -> Option<ImageGenerator> + Send + Sync + 'static,
+) {
+    *IMAGE_GENERATOR_FACTORY.write().unwrap() = Some(Arc::new(factory));
+    unsafe {
+        sb::C_SkGraphics_SetImageGeneratorFromEncodedDataFactory(Some(
+            image_generator_from_encoded_data,
+        ))
+    }
+}
+
+/// Removes the factory set with [`set_image_generator_from_encoded_data_factory()`].
+pub fn reset_image_generator_from_encoded_data_factory() {
+    unsafe { sb::C_SkGraphics_SetImageGeneratorFromEncodedDataFactory(None) }
+    *IMAGE_GENERATOR_FACTORY.write().unwrap() = None;
+}
+
+extern "C" fn image_generator_from_encoded_data(data: *mut SkData) -> *mut SkImageGenerator {
+    let data = Data::from_ptr(data).unwrap();
+    // the factory is called without holding the lock, so that it can be replaced meanwhile.
+    let factory = IMAGE_GENERATOR_FACTORY
+        .read()
+        .ok()
+        .and_then(|factory| factory.clone());
+    match factory {
+        Some(factory) => factory(data)
+            .map(|generator| generator.into_ptr())
+            .unwrap_or(std::ptr::null_mut()),
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        reset_image_generator_from_encoded_data_factory,
+        set_image_generator_from_encoded_data_factory,
+    };
+    use crate::{
+        AlphaType, ColorType, CustomImageGenerator, Data, Image, ImageGenerator, ImageInfo,
+    };
+
+    const MAGIC: &[u8] = b"GRAY";
+
+    // A minimal format Skia does not know: the magic, the width and height in bytes, and the
+    // gray pixels.
+    struct Gray {
+        data: Data,
+    }
+
+    impl CustomImageGenerator for Gray {
+        fn info(&self) -> ImageInfo {
+            let bytes = self.data.as_bytes();
+            ImageInfo::new(
+                (bytes[4] as i32, bytes[5] as i32),
+                ColorType::Gray8,
+                AlphaType::Opaque,
+                None,
+            )
+        }
+
+        fn encoded_data(&mut self) -> Option<Data> {
+            Some(self.data.clone())
+        }
+
+        fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::Gray8 {
+                return false;
+            }
+            let width = info.width() as usize;
+            let src = &self.data.as_bytes()[6..];
+            for (dst, src) in pixels.chunks_mut(row_bytes).zip(src.chunks(width)) {
+                dst[..width].copy_from_slice(src);
+            }
+            true
+        }
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn image_from_encoded_uses_factory() {
+        let mut encoded = MAGIC.to_vec();
+        encoded.extend_from_slice(&[2, 2, 10, 20, 30, 40]);
+        let encoded = Data::new_copy(&encoded);
+        assert!(Image::from_encoded(encoded.clone(), None).is_none());
+
+        set_image_generator_from_encoded_data_factory(|data| {
+            if data.as_bytes().starts_with(MAGIC) {
+                Some(ImageGenerator::from_custom(Gray { data }))
+            } else {
+                None
+            }
+        });
+        let image = Image::from_encoded(encoded.clone(), None);
+        reset_image_generator_from_encoded_data_factory();
+
+        let image = image.unwrap();
+        assert_eq!((2, 2), (image.width(), image.height()));
+        let raster = image.new_raster_image().unwrap();
+        let pixmap = raster.peek_pixels().unwrap();
+        assert_eq!(ColorType::Gray8, pixmap.color_type());
+        assert!(Image::from_encoded(encoded, None).is_none());
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn factory_can_reset_itself() {
+        let mut encoded = MAGIC.to_vec();
+        encoded.extend_from_slice(&[1, 1, 10]);
+        let encoded = Data::new_copy(&encoded);
+
+        set_image_generator_from_encoded_data_factory(|data| {
+            reset_image_generator_from_encoded_data_factory();
+            Some(ImageGenerator::from_custom(Gray { data }))
+        });
+        assert!(Image::from_encoded(encoded.clone(), None).is_some());
+        assert!(Image::from_encoded(encoded, None).is_none());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use crate::{gpu, FilterQuality, ImageFilter, ImageGenerator, Pixmap};
+use crate::{encode, gpu, FilterQuality, ImageFilter, ImageGenerator, Pixmap};
 use crate::{
     AlphaType, Bitmap, ColorSpace, ColorType, Data, EncodedImageFormat, IPoint, IRect, ISize,
     ImageInfo, Matrix, Paint, Picture, Shader, TileMode, YUVAIndex, YUVColorSpace,
@@ -8,7 +8,7 @@ use skia_bindings as sb;
 use skia_bindings::{
     SkImage, SkImage_BitDepth, SkImage_CachingHint, SkImage_CompressionType, SkRefCntBase,
 };
-use std::mem;
+use std::{io, mem};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 #[repr(i32)]
@@ -471,13 +471,40 @@ impl RCHandle<SkImage> {
         }
     }
 
-    // TODO: support quality!
     pub fn encode_to_data(&self, image_format: EncodedImageFormat) -> Option<Data> {
+        self.encode_to_data_with_quality(image_format, 100)
+    }
+
+    /// Encodes the image with a `quality` from 0 to 100, which is used by the lossy formats.
+    /// Higher values are treated as 100.
+    pub fn encode_to_data_with_quality(
+        &self,
+        image_format: EncodedImageFormat,
+        quality: usize,
+    ) -> Option<Data> {
         Data::from_ptr(unsafe {
-            sb::C_SkImage_encodeToData(self.native(), image_format.into_native())
+            sb::C_SkImage_encodeToData(
+                self.native(),
+                image_format.into_native(),
+                quality.min(100) as i32,
+            )
         })
     }
 
+    /// Encodes the image with the encoder the `options` belong to.
+    pub fn encode_with(&self, options: &impl encode::EncoderOptions) -> Option<Data> {
+        options.encode_image(self)
+    }
+
+    /// Encodes the image with the encoder the `options` belong to into `writer`.
+    pub fn encode_to<W: io::Write>(
+        &self,
+        writer: W,
+        options: &impl encode::EncoderOptions,
+    ) -> io::Result<W> {
+        options.encode_image_to(self, writer)
+    }
+
     pub fn encoded_data(&self) -> Option<Data> {
         Data::from_ptr(unsafe { sb::C_SkImage_refEncodedData(self.native()) })
     }
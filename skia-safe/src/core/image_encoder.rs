@@ -1,43 +1,45 @@
+use crate::encode::EncoderOptions;
 use crate::prelude::Handle;
 use crate::{Data, EncodedImageFormat};
 use skia_bindings::{SkBitmap, SkPixmap};
+use std::io;
 
 impl Handle<SkPixmap> {
     pub fn encode(&self, format: EncodedImageFormat, quality: usize) -> Option<Data> {
         crate::encode::pixmap(self, format, quality)
     }
+
+    /// Encodes the pixels with the encoder the `options` belong to.
+    pub fn encode_with(&self, options: &impl EncoderOptions) -> Option<Data> {
+        options.encode_pixmap(self)
+    }
+
+    /// Encodes the pixels with the encoder the `options` belong to into `writer`.
+    pub fn encode_to<W: io::Write>(
+        &self,
+        writer: W,
+        options: &impl EncoderOptions,
+    ) -> io::Result<W> {
+        options.encode_pixmap_to(self, writer)
+    }
 }
 
 impl Handle<SkBitmap> {
     pub fn encode(&self, format: EncodedImageFormat, quality: usize) -> Option<Data> {
         crate::encode::bitmap(self, format, quality)
     }
-}
-
-pub mod encode {
-    // TODO: wrap stream variants.
-
-    use crate::prelude::*;
-    use crate::{Bitmap, Data, EncodedImageFormat, Pixmap};
-    use skia_bindings as sb;
 
-    pub fn pixmap(src: &Pixmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
-        Data::from_ptr(unsafe {
-            sb::C_SkEncodePixmap(
-                src.native(),
-                format.into_native(),
-                quality.try_into().unwrap(),
-            )
-        })
+    /// Encodes the pixels with the encoder the `options` belong to.
+    pub fn encode_with(&self, options: &impl EncoderOptions) -> Option<Data> {
+        options.encode_bitmap(self)
     }
 
-    pub fn bitmap(src: &Bitmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
-        Data::from_ptr(unsafe {
-            sb::C_SkEncodeBitmap(
-                src.native(),
-                format.into_native(),
-                quality.try_into().unwrap(),
-            )
-        })
+    /// Encodes the pixels with the encoder the `options` belong to into `writer`.
+    pub fn encode_to<W: io::Write>(
+        &self,
+        writer: W,
+        options: &impl EncoderOptions,
+    ) -> io::Result<W> {
+        options.encode_bitmap_to(self, writer)
     }
 }
@@ -4,8 +4,12 @@ use crate::{
     YUVASizeInfo, YUVColorSpace,
 };
 use skia_bindings as sb;
-use skia_bindings::SkImageGenerator;
+use skia_bindings::{
+    RustImageGenerator_Param, SkData, SkImageGenerator, SkImageInfo, SkYUVAIndex, SkYUVASizeInfo,
+    SkYUVColorSpace,
+};
 use std::ffi::c_void;
+use std::slice;
 
 pub type ImageGenerator = RefHandle<SkImageGenerator>;
 
@@ -102,6 +106,14 @@ impl RefHandle<SkImageGenerator> {
 
     // TODO: generateTexture()
 
+    /// Creates an image generator that decodes the image with `generator`.
+    ///
+    /// Images created from the generator with [`crate::Image::from_generator()`] are decoded
+    /// lazily and their pixels are cached like the pixels of images Skia decodes.
+    pub fn from_custom(generator: impl CustomImageGenerator + 'static) -> Self {
+        new_custom(generator)
+    }
+
     pub fn from_encoded(encoded: Data) -> Option<Self> {
         Self::from_ptr(unsafe { sb::C_SkImageGenerator_MakeFromEncoded(encoded.into_ptr()) })
     }
@@ -126,3 +138,198 @@ impl RefHandle<SkImageGenerator> {
         })
     }
 }
+
+/// An image decoder implemented in Rust, for example for formats Skia does not support.
+///
+/// Use [`ImageGenerator::from_custom()`] to create an [`ImageGenerator`] from it.
+pub trait CustomImageGenerator: Send {
+    /// The info of the image the generator produces.
+    ///
+    /// This is called only once, when the [`ImageGenerator`] is created.
+    fn info(&self) -> ImageInfo;
+
+    /// Returns the encoded image, if there is one.
+    fn encoded_data(&mut self) -> Option<Data> {
+        None
+    }
+
+    /// Decodes the image into `pixels`, which contains `info.height()` rows of `row_bytes`.
+    ///
+    /// `info` has the dimensions of the image, but its color type, alpha type or color space
+    /// may differ from the ones returned by [`Self::info()`]. Returns `false` if the generator
+    /// does not support the conversion, or the image could not be decoded.
+    fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool;
+
+    /// Returns the sizes of the YUVA planes, which planes and channels the Y, U, V and A values
+    /// are stored in, and the color space, if the generator supports decoding to YUVA planes.
+    fn query_yuva8(
+        &self,
+    ) -> Option<(
+        YUVASizeInfo,
+        [YUVAIndex; YUVAIndex::INDEX_COUNT],
+        YUVColorSpace,
+    )> {
+        None
+    }
+
+    /// Decodes the image into the YUVA planes that were returned by [`Self::query_yuva8()`].
+    ///
+    /// `planes` contains a slice for each of the planes, with unused planes being empty.
+    fn get_yuva8_planes(
+        &mut self,
+        _size_info: &YUVASizeInfo,
+        _yuva_indices: &[YUVAIndex; YUVAIndex::INDEX_COUNT],
+        _planes: &mut [&mut [u8]],
+    ) -> bool {
+        false
+    }
+}
+
+fn new_custom<G: CustomImageGenerator + 'static>(generator: G) -> ImageGenerator {
+    let info = generator.info();
+    let param = RustImageGenerator_Param {
+        data: Box::into_raw(Box::new(generator)) as _,
+        refEncodedData: Some(custom_ref_encoded_data::<G>),
+        getPixels: Some(custom_get_pixels::<G>),
+        queryYUVA8: Some(custom_query_yuva8::<G>),
+        getYUVA8Planes: Some(custom_get_yuva8_planes::<G>),
+        drop: Some(custom_drop::<G>),
+    };
+    ImageGenerator::from_ptr(unsafe { sb::C_RustImageGenerator_new(info.native(), &param) })
+        .unwrap()
+}
+
+extern "C" fn custom_ref_encoded_data<G: CustomImageGenerator>(data: *mut c_void) -> *mut SkData {
+    let generator = unsafe { &mut *(data as *mut G) };
+    generator.encoded_data().into_ptr_or_null()
+}
+
+extern "C" fn custom_get_pixels<G: CustomImageGenerator>(
+    data: *mut c_void,
+    info: *const SkImageInfo,
+    pixels: *mut c_void,
+    row_bytes: usize,
+) -> bool {
+    let generator = unsafe { &mut *(data as *mut G) };
+    let info = ImageInfo::from_native_ref(unsafe { &*info });
+    let pixels =
+        unsafe { slice::from_raw_parts_mut(pixels as *mut u8, info.compute_byte_size(row_bytes)) };
+    generator.get_pixels(info, pixels, row_bytes)
+}
+
+extern "C" fn custom_query_yuva8<G: CustomImageGenerator>(
+    data: *mut c_void,
+    size_info: *mut SkYUVASizeInfo,
+    yuva_indices: *mut SkYUVAIndex,
+    color_space: *mut SkYUVColorSpace,
+) -> bool {
+    let generator = unsafe { &*(data as *const G) };
+    match generator.query_yuva8() {
+        Some((info, indices, cs)) => {
+            unsafe {
+                *size_info = info.into_native();
+                for (i, index) in indices.iter().enumerate() {
+                    *yuva_indices.add(i) = index.into_native();
+                }
+                *color_space = cs.into_native();
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+extern "C" fn custom_get_yuva8_planes<G: CustomImageGenerator>(
+    data: *mut c_void,
+    size_info: *const SkYUVASizeInfo,
+    yuva_indices: *const SkYUVAIndex,
+    planes: *mut *mut c_void,
+) -> bool {
+    let generator = unsafe { &mut *(data as *mut G) };
+    let size_info = YUVASizeInfo::from_native_ref(unsafe { &*size_info });
+    let yuva_indices = unsafe {
+        &*(yuva_indices as *const [SkYUVAIndex; YUVAIndex::INDEX_COUNT]
+            as *const [YUVAIndex; YUVAIndex::INDEX_COUNT])
+    };
+    let mut planes: Vec<&mut [u8]> = (0..YUVASizeInfo::MAX_COUNT)
+        .map(|i| {
+            let plane = unsafe { *planes.add(i) } as *mut u8;
+            if plane.is_null() {
+                return &mut [][..];
+            }
+            let size = size_info.width_bytes[i] * size_info.sizes[i].height as usize;
+            unsafe { slice::from_raw_parts_mut(plane, size) }
+        })
+        .collect();
+    generator.get_yuva8_planes(size_info, yuva_indices, &mut planes)
+}
+
+extern "C" fn custom_drop<G: CustomImageGenerator>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut G) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CustomImageGenerator;
+    use crate::image::CachingHint;
+    use crate::{AlphaType, ColorType, Image, ImageGenerator, ImageInfo};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Checkerboard {
+        decoded: Arc<AtomicUsize>,
+    }
+
+    impl CustomImageGenerator for Checkerboard {
+        fn info(&self) -> ImageInfo {
+            ImageInfo::new((8, 8), ColorType::Gray8, AlphaType::Opaque, None)
+        }
+
+        fn get_pixels(&mut self, info: &ImageInfo, pixels: &mut [u8], row_bytes: usize) -> bool {
+            if info.color_type() != ColorType::Gray8 {
+                return false;
+            }
+            for (y, row) in pixels.chunks_mut(row_bytes).enumerate() {
+                for (x, pixel) in row[..8].iter_mut().enumerate() {
+                    *pixel = if (x + y) % 2 == 0 { 0xff } else { 0 };
+                }
+            }
+            self.decoded.fetch_add(1, Ordering::SeqCst);
+            true
+        }
+    }
+
+    #[test]
+    fn image_decodes_lazily_with_custom_generator() {
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let generator = ImageGenerator::from_custom(Checkerboard {
+            decoded: decoded.clone(),
+        });
+        assert_eq!(8, generator.info().width());
+        let image = Image::from_generator(generator, None).unwrap();
+        assert!(image.is_lazy_generated());
+        assert_eq!(0, decoded.load(Ordering::SeqCst));
+
+        let info = image.image_info().clone();
+        let mut pixels = vec![0u8; 64];
+        assert!(image.read_pixels(&info, &mut pixels, 8, (0, 0), CachingHint::Allow));
+        assert_eq!([0xff, 0, 0xff], pixels[..3]);
+        assert_eq!([0, 0xff], pixels[8..10]);
+        assert_eq!(1, decoded.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn custom_generator_is_dropped_with_image() {
+        let decoded = Arc::new(AtomicUsize::new(0));
+        let image = Image::from_generator(
+            ImageGenerator::from_custom(Checkerboard {
+                decoded: decoded.clone(),
+            }),
+            None,
+        )
+        .unwrap();
+        assert_eq!(2, Arc::strong_count(&decoded));
+        drop(image);
+        assert_eq!(1, Arc::strong_count(&decoded));
+    }
+}
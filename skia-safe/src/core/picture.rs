@@ -1,8 +1,10 @@
+use crate::interop::Stream;
 use crate::prelude::*;
 use crate::{Canvas, Data, Rect};
 use crate::{Matrix, Shader, TileMode};
 use skia_bindings as sb;
 use skia_bindings::{SkPicture, SkRefCntBase};
+use std::io;
 
 pub type Picture = RCHandle<SkPicture>;
 
@@ -11,7 +13,11 @@ impl NativeRefCountedBase for SkPicture {
 }
 
 impl RCHandle<SkPicture> {
-    // TODO: wrap MakeFromStream
+    /// Reads a serialized picture from `reader`.
+    pub fn from_stream(reader: impl io::Read + Send + 'static) -> Option<Picture> {
+        let mut stream = Stream::from_read(reader);
+        Picture::from_ptr(unsafe { sb::C_SkPicture_MakeFromStream(stream.native_mut()) })
+    }
 
     // TODO: may support SkSerialProces in MakeFromData?
 
@@ -90,3 +96,14 @@ impl RCHandle<SkPicture> {
         .unwrap()
     }
 }
+
+#[test]
+fn picture_from_stream_reads_serialized_picture() {
+    let mut recorder = crate::PictureRecorder::new();
+    let canvas = recorder.begin_recording(Rect::new(0.0, 0.0, 10.0, 20.0), None, None);
+    canvas.draw_rect(Rect::new(2.0, 2.0, 8.0, 8.0), &crate::Paint::default());
+    let picture = recorder.finish_recording_as_picture(None).unwrap();
+    let serialized = picture.serialize().as_bytes().to_vec();
+    let picture = Picture::from_stream(io::Cursor::new(serialized)).unwrap();
+    assert_eq!(Rect::new(0.0, 0.0, 10.0, 20.0), picture.cull_rect());
+}
@@ -41,9 +41,26 @@ impl Handle<SkPixmap> {
         assert!(pixels.len() >= height * row_bytes);
 
         let pm = Pixmap::from_native(SkPixmap {
-            fPixels: ptr::null(),
-            fRowBytes: 0,
-            fInfo: ImageInfo::new_unknown(None).native().clone(),
+            fPixels: pixels.as_ptr() as _,
+            fRowBytes: row_bytes,
+            fInfo: info.native().clone(),
+        });
+        pm.borrows(pixels)
+    }
+
+    /// Creates a pixmap that Skia may write the `pixels` through.
+    pub(crate) fn new_mut<'pixels>(
+        info: &ImageInfo,
+        pixels: &'pixels mut [u8],
+        row_bytes: usize,
+    ) -> Borrows<'pixels, Self> {
+        assert!(info.valid_row_bytes(row_bytes));
+        assert!(pixels.len() >= info.compute_byte_size(row_bytes));
+
+        let pm = Pixmap::from_native(SkPixmap {
+            fPixels: pixels.as_mut_ptr() as _,
+            fRowBytes: row_bytes,
+            fInfo: info.native().clone(),
         });
         pm.borrows(pixels)
     }
@@ -239,3 +256,15 @@ impl Handle<SkPixmap> {
         }
     }
 }
+
+#[test]
+fn new_refers_to_pixels_and_info() {
+    let info = ImageInfo::new_n32_premul((2, 3), None);
+    let pixels = [0xffu8; 2 * 3 * 4];
+    let pixmap = Pixmap::new(&info, &pixels, 2 * 4);
+    assert_eq!(pixmap.dimensions(), ISize::new(2, 3));
+    assert_eq!(pixmap.color_type(), info.color_type());
+    assert_eq!(pixmap.row_bytes(), 8);
+    assert_eq!(unsafe { pixmap.addr() }, pixels.as_ptr() as *const c_void);
+    assert_eq!(pixmap.get_color((1, 2)), Color::WHITE);
+}
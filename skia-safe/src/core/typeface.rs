@@ -1,4 +1,4 @@
-use crate::interop::{MemoryStream, NativeStreamBase};
+use crate::interop::{MemoryStream, NativeStreamBase, StreamAsset};
 use crate::prelude::*;
 use crate::{font_arguments, interop, FontArguments};
 use crate::{font_parameters::VariationAxis, Data, FontStyle, GlyphId, Rect, Unichar};
@@ -6,7 +6,7 @@ use skia_bindings as sb;
 use skia_bindings::{
     SkRefCntBase, SkTypeface, SkTypeface_LocalizedStrings, SkTypeface_SerializeBehavior,
 };
-use std::{ffi, ptr};
+use std::{ffi, io, ptr};
 
 pub type FontId = skia_bindings::SkFontID;
 pub type FontTableTag = skia_bindings::SkFontTableTag;
@@ -123,7 +123,22 @@ impl RCHandle<SkTypeface> {
     // from_file is unsupported, because it is unclear what the
     // encoding of the path name is. from_data can be used instead.
 
-    // TODO: MakeFromStream()?
+    /// Creates a typeface that reads the font data from `reader` when needed, instead of
+    /// loading it into memory.
+    ///
+    /// Returns `None` if the reader can not be seeked or does not contain a supported font.
+    pub fn from_stream(
+        reader: impl io::Read + io::Seek + Send + 'static,
+        index: impl Into<Option<usize>>,
+    ) -> Option<Typeface> {
+        let stream = StreamAsset::from_read_seek(reader).ok()?;
+        Typeface::from_ptr(unsafe {
+            sb::C_SkTypeface_MakeFromStream(
+                stream.into_ptr(),
+                index.into().unwrap_or_default().try_into().unwrap(),
+            )
+        })
+    }
 
     pub fn from_data(data: Data, index: impl Into<Option<usize>>) -> Option<Typeface> {
         Typeface::from_ptr(unsafe {
@@ -322,3 +337,62 @@ fn family_name_iterator_owns_the_strings_and_returns_at_least_one_name_for_the_d
     }
     assert!(any);
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::Typeface;
+    use std::io;
+
+    /// Assembles a font file from the tables of `typeface`.
+    ///
+    /// The checksums and the binary search fields of the table directory are left at 0, because
+    /// they are not verified when a font is loaded.
+    pub(crate) fn font_file(typeface: &Typeface) -> Vec<u8> {
+        let mut tables: Vec<(u32, Vec<u8>)> = typeface
+            .table_tags()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|tag| Some((tag, typeface.copy_table_data(tag)?.as_bytes().to_vec())))
+            .collect();
+        tables.sort_by_key(|(tag, _)| *tag);
+
+        let cff = u32::from_be_bytes(*b"CFF ");
+        let version = if tables.iter().any(|(tag, _)| *tag == cff) {
+            u32::from_be_bytes(*b"OTTO")
+        } else {
+            0x0001_0000
+        };
+        let mut file = Vec::new();
+        file.extend(&version.to_be_bytes());
+        file.extend(&(tables.len() as u16).to_be_bytes());
+        file.extend(&[0u8; 6]);
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in &tables {
+            file.extend(&tag.to_be_bytes());
+            file.extend(&0u32.to_be_bytes());
+            file.extend(&(offset as u32).to_be_bytes());
+            file.extend(&(data.len() as u32).to_be_bytes());
+            offset += (data.len() + 3) & !3;
+        }
+        for (_, data) in &tables {
+            file.extend(data);
+            file.resize((file.len() + 3) & !3, 0);
+        }
+        file
+    }
+
+    #[test]
+    fn from_stream_loads_a_font_from_a_reader() {
+        let typeface = Typeface::default();
+        let reader = io::Cursor::new(font_file(&typeface));
+        let loaded = Typeface::from_stream(reader, None).unwrap();
+        assert_eq!(typeface.count_glyphs(), loaded.count_glyphs());
+        assert_eq!(typeface.family_name(), loaded.family_name());
+    }
+
+    #[test]
+    fn from_stream_rejects_a_reader_without_a_font() {
+        let reader = io::Cursor::new(vec![0u8; 64]);
+        assert!(Typeface::from_stream(reader, None).is_none());
+    }
+}
@@ -44,6 +44,12 @@ impl FourByteTag {
         Self(v)
     }
 
+    /// Creates a tag from its four characters, for example `(b'L', b'a', b't', b'n')`, like
+    /// `SkSetFourByteTag()` does.
+    pub fn from_chars(a: u8, b: u8, c: u8, d: u8) -> Self {
+        Self::new(u32::from_be_bytes([a, b, c, d]))
+    }
+
     pub fn a(self) -> u8 {
         (self.into_native() >> 24) as u8
     }
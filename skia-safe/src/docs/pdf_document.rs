@@ -1,12 +1,126 @@
 pub mod pdf {
-    use crate::interop::DynamicMemoryWStream;
+    use crate::interop::{DynamicMemoryWStream, WriterStream};
     use crate::prelude::*;
-    use crate::{scalar, DateTime, Document};
+    use crate::{scalar, Canvas, DateTime, Document, Executor};
     use skia_bindings as sb;
-    use skia_bindings::{SkPDF_Metadata, SkString};
+    use skia_bindings::{
+        SkPDF_DocumentStructureType, SkPDF_Metadata, SkPDF_Metadata_Subsetter,
+        SkPDF_StructureElementNode, SkString, SkWStream,
+    };
+    use std::{io, ptr};
 
-    // TODO: DocumentStructureType
-    // TODO: StructureElementNode
+    /// The type of a structure element in a tagged PDF, see section 14.8.4 of the PDF 1.7
+    /// specification.
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    #[repr(i32)]
+    pub enum DocumentStructureType {
+        Document = SkPDF_DocumentStructureType::kDocument as _,
+        Part = SkPDF_DocumentStructureType::kPart as _,
+        Art = SkPDF_DocumentStructureType::kArt as _,
+        Sect = SkPDF_DocumentStructureType::kSect as _,
+        Div = SkPDF_DocumentStructureType::kDiv as _,
+        BlockQuote = SkPDF_DocumentStructureType::kBlockQuote as _,
+        Caption = SkPDF_DocumentStructureType::kCaption as _,
+        TOC = SkPDF_DocumentStructureType::kTOC as _,
+        TOCI = SkPDF_DocumentStructureType::kTOCI as _,
+        Index = SkPDF_DocumentStructureType::kIndex as _,
+        NonStruct = SkPDF_DocumentStructureType::kNonStruct as _,
+        Private = SkPDF_DocumentStructureType::kPrivate as _,
+        H = SkPDF_DocumentStructureType::kH as _,
+        H1 = SkPDF_DocumentStructureType::kH1 as _,
+        H2 = SkPDF_DocumentStructureType::kH2 as _,
+        H3 = SkPDF_DocumentStructureType::kH3 as _,
+        H4 = SkPDF_DocumentStructureType::kH4 as _,
+        H5 = SkPDF_DocumentStructureType::kH5 as _,
+        H6 = SkPDF_DocumentStructureType::kH6 as _,
+        P = SkPDF_DocumentStructureType::kP as _,
+        L = SkPDF_DocumentStructureType::kL as _,
+        LI = SkPDF_DocumentStructureType::kLI as _,
+        Lbl = SkPDF_DocumentStructureType::kLbl as _,
+        LBody = SkPDF_DocumentStructureType::kLBody as _,
+        Table = SkPDF_DocumentStructureType::kTable as _,
+        TR = SkPDF_DocumentStructureType::kTR as _,
+        TH = SkPDF_DocumentStructureType::kTH as _,
+        TD = SkPDF_DocumentStructureType::kTD as _,
+        THead = SkPDF_DocumentStructureType::kTHead as _,
+        TBody = SkPDF_DocumentStructureType::kTBody as _,
+        TFoot = SkPDF_DocumentStructureType::kTFoot as _,
+        Span = SkPDF_DocumentStructureType::kSpan as _,
+        Quote = SkPDF_DocumentStructureType::kQuote as _,
+        Note = SkPDF_DocumentStructureType::kNote as _,
+        Reference = SkPDF_DocumentStructureType::kReference as _,
+        BibEntry = SkPDF_DocumentStructureType::kBibEntry as _,
+        Code = SkPDF_DocumentStructureType::kCode as _,
+        Link = SkPDF_DocumentStructureType::kLink as _,
+        Annot = SkPDF_DocumentStructureType::kAnnot as _,
+        Ruby = SkPDF_DocumentStructureType::kRuby as _,
+        RB = SkPDF_DocumentStructureType::kRB as _,
+        RT = SkPDF_DocumentStructureType::kRT as _,
+        RP = SkPDF_DocumentStructureType::kRP as _,
+        Warichu = SkPDF_DocumentStructureType::kWarichu as _,
+        WT = SkPDF_DocumentStructureType::kWT as _,
+        WP = SkPDF_DocumentStructureType::kWP as _,
+        Figure = SkPDF_DocumentStructureType::kFigure as _,
+        Formula = SkPDF_DocumentStructureType::kFormula as _,
+        Form = SkPDF_DocumentStructureType::kForm as _,
+    }
+
+    impl NativeTransmutable<SkPDF_DocumentStructureType> for DocumentStructureType {}
+    #[test]
+    fn test_document_structure_type_layout() {
+        DocumentStructureType::test_layout()
+    }
+
+    /// A node in the structure element tree of a tagged PDF.
+    ///
+    /// Drawing is associated with a node by calling [`set_node_id()`] with the node's id before
+    /// drawing.
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    pub struct StructureElementNode {
+        pub node_type: DocumentStructureType,
+        pub children: Vec<StructureElementNode>,
+        /// The id that associates drawing with this node, which must be unique in the tree.
+        pub node_id: i32,
+    }
+
+    impl StructureElementNode {
+        pub fn new(node_type: DocumentStructureType, node_id: i32) -> Self {
+            StructureElementNode {
+                node_type,
+                children: Vec::new(),
+                node_id,
+            }
+        }
+
+        pub fn with_children(mut self, children: impl IntoIterator<Item = Self>) -> Self {
+            self.children.extend(children);
+            self
+        }
+    }
+
+    /// The library that is used to subset the embedded fonts.
+    ///
+    /// Only HarfBuzz is built, and only if the `shaper` feature is enabled (which `textlayout`
+    /// enables, too). If the selected subsetter is not available, another one is used. Without
+    /// the `shaper` feature, no subsetter is available and the fonts are embedded completely.
+    #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+    #[repr(i32)]
+    pub enum Subsetter {
+        Harfbuzz = SkPDF_Metadata_Subsetter::kHarfbuzz_Subsetter as _,
+        Sfntly = SkPDF_Metadata_Subsetter::kSfntly_Subsetter as _,
+    }
+
+    impl NativeTransmutable<SkPDF_Metadata_Subsetter> for Subsetter {}
+    #[test]
+    fn test_subsetter_layout() {
+        Subsetter::test_layout()
+    }
+
+    impl Default for Subsetter {
+        fn default() -> Self {
+            Subsetter::Harfbuzz
+        }
+    }
 
     #[derive(Clone, Debug, Default)]
     pub struct Metadata {
@@ -21,13 +135,52 @@ pub mod pdf {
         pub raster_dpi: Option<scalar>,
         pub pdfa: bool,
         pub encoding_quality: Option<i32>,
-        // TODO: fStructureElementTreeRoot
+        /// The root of the structure element tree. If set, the document is a tagged PDF.
+        pub structure_element_tree_root: Option<StructureElementNode>,
+        /// If set, the document compresses and serializes pages, images and fonts in parallel
+        /// on the executor. The output does not depend on the executor.
+        pub executor: Option<Executor>,
+        pub subsetter: Subsetter,
     }
 
-    // TODO: SetNodeId
+    /// Associates the drawing that follows on `canvas` with the [`StructureElementNode`] of
+    /// the same `node_id`.
+    ///
+    /// `canvas` must be the canvas of a page of a PDF document.
+    pub fn set_node_id(canvas: &mut Canvas, node_id: i32) {
+        unsafe { sb::C_SkPDF_SetNodeId(canvas.native_mut(), node_id) }
+    }
 
     pub fn new_document(metadata: Option<&Metadata>) -> Document {
+        // we can't move the memory stream around anymore as soon it's referred by
+        // the document.
+        let mut memory_stream = Box::pin(DynamicMemoryWStream::new());
+        let document = make_document(memory_stream.native_mut().base_mut(), metadata);
+
+        Document::new(memory_stream, document, executor(metadata))
+    }
+
+    /// Creates a document that is written to `writer` while the pages are drawn, so that the
+    /// document does not need to be kept in memory.
+    ///
+    /// [`Document::close()`] returns the writer, or the first I/O error the writer returned.
+    pub fn new_document_to<W: io::Write>(
+        writer: W,
+        metadata: Option<&Metadata>,
+    ) -> Document<crate::document::state::Open, WriterStream<W>> {
+        let mut stream = Box::pin(WriterStream::new(writer));
+        let document = make_document(stream.stream_mut(), metadata);
+
+        Document::new(stream, document, executor(metadata))
+    }
+
+    fn make_document(
+        stream: &mut SkWStream,
+        metadata: Option<&Metadata>,
+    ) -> RCHandle<sb::SkDocument> {
         let mut md = InternalMetadata::default();
+        // the document copies the tree, so it needs to live only until the document is made.
+        let mut tree = NativeTree::default();
         if let Some(metadata) = metadata {
             let internal = md.native_mut();
             internal.fTitle.set_s(&metadata.title);
@@ -49,17 +202,52 @@ pub mod pdf {
             if let Some(encoding_quality) = metadata.encoding_quality {
                 internal.fEncodingQuality = encoding_quality
             }
+            if let Some(root) = &metadata.structure_element_tree_root {
+                internal.fStructureElementTreeRoot = tree.root(root);
+            }
+            if let Some(executor) = &metadata.executor {
+                internal.fExecutor = executor.native_ptr();
+            }
+            internal.fSubsetter = metadata.subsetter.into_native();
         }
 
-        // we can't move the memory stream around anymore as soon it's referred by
-        // the document.
-        let mut memory_stream = Box::pin(DynamicMemoryWStream::new());
-        let document = RCHandle::from_ptr(unsafe {
-            sb::C_SkPDF_MakeDocument(memory_stream.native_mut().base_mut(), md.native())
-        })
-        .unwrap();
+        RCHandle::from_ptr(unsafe { sb::C_SkPDF_MakeDocument(stream, md.native()) }).unwrap()
+    }
 
-        Document::new(memory_stream, document)
+    fn executor(metadata: Option<&Metadata>) -> Option<Executor> {
+        metadata.and_then(|metadata| metadata.executor.clone())
+    }
+
+    // The native nodes of a structure element tree. The children of each node are stored in
+    // their own vector, which does not move when more vectors are added.
+    #[derive(Default)]
+    struct NativeTree {
+        nodes: Vec<Vec<SkPDF_StructureElementNode>>,
+    }
+
+    impl NativeTree {
+        fn root(&mut self, root: &StructureElementNode) -> *const SkPDF_StructureElementNode {
+            let root = vec![self.node(root)];
+            self.nodes.push(root);
+            self.nodes.last().unwrap().as_ptr()
+        }
+
+        fn node(&mut self, node: &StructureElementNode) -> SkPDF_StructureElementNode {
+            let children: Vec<SkPDF_StructureElementNode> =
+                node.children.iter().map(|child| self.node(child)).collect();
+            let native = SkPDF_StructureElementNode {
+                fChildren: if children.is_empty() {
+                    ptr::null()
+                } else {
+                    children.as_ptr()
+                },
+                fChildCount: children.len(),
+                fNodeId: node.node_id,
+                fType: node.node_type.into_native(),
+            };
+            self.nodes.push(children);
+            native
+        }
     }
 
     //
@@ -89,4 +277,173 @@ pub mod pdf {
             unsafe { self.set1(bytes.as_ptr() as _, bytes.len()) }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{
+            new_document, new_document_to, set_node_id, DocumentStructureType, Metadata,
+            StructureElementNode, Subsetter,
+        };
+        use crate::interop::FailingWriter;
+        use crate::{Color, Executor, Font, FontMgr, Paint, Rect, Surface};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        fn draw_pages<Stream>(
+            document: crate::Document<crate::document::state::Open, Stream>,
+        ) -> crate::Document<crate::document::state::Open, Stream> {
+            let mut page = document.begin_page((100, 100), None);
+            page.canvas().draw_circle((50, 50), 40.0, &Paint::default());
+            page.end_page()
+        }
+
+        #[test]
+        fn document_is_written_to_writer() {
+            let pdf = draw_pages(new_document_to(Vec::new(), None))
+                .close()
+                .unwrap();
+            assert!(pdf.starts_with(b"%PDF"));
+            assert!(pdf.ends_with(b"%%EOF\n"));
+            let in_memory = draw_pages(new_document(None)).close();
+            assert!(in_memory.as_bytes().starts_with(b"%PDF"));
+        }
+
+        #[test]
+        fn document_close_returns_writer_error() {
+            let error = draw_pages(new_document_to(FailingWriter, None))
+                .close()
+                .err()
+                .unwrap();
+            assert_eq!("disk full", error.to_string());
+        }
+
+        #[test]
+        fn tagged_document_contains_structure_tree() {
+            let metadata = Metadata {
+                structure_element_tree_root: Some(
+                    StructureElementNode::new(DocumentStructureType::Document, 1).with_children(
+                        vec![
+                            StructureElementNode::new(DocumentStructureType::H1, 2),
+                            StructureElementNode::new(DocumentStructureType::Figure, 3),
+                        ],
+                    ),
+                ),
+                ..Metadata::default()
+            };
+            let mut page = new_document(Some(&metadata)).begin_page((100, 100), None);
+            let canvas = page.canvas();
+            set_node_id(canvas, 2);
+            canvas.draw_rect(Rect::new(10.0, 10.0, 90.0, 20.0), &Paint::default());
+            set_node_id(canvas, 3);
+            canvas.draw_circle((50, 60), 20.0, &Paint::default());
+            let pdf = page.end_page().close();
+
+            let contains = |s: &[u8]| pdf.as_bytes().windows(s.len()).any(|w| w == s);
+            assert!(contains(b"/StructTreeRoot"));
+            assert!(contains(b"/MarkInfo"));
+            assert!(contains(b"/Figure"));
+        }
+
+        #[test]
+        fn document_runs_jobs_on_executor() {
+            let jobs = Arc::new(AtomicUsize::new(0));
+            let executor = {
+                let jobs = jobs.clone();
+                Executor::from_fn(move |work| {
+                    jobs.fetch_add(1, Ordering::SeqCst);
+                    std::thread::spawn(move || work.run());
+                })
+            };
+            let metadata = Metadata {
+                executor: Some(executor),
+                ..Metadata::default()
+            };
+
+            let mut surface = Surface::new_raster_n32_premul((64, 64)).unwrap();
+            surface.canvas().clear(Color::BLUE);
+            let image = surface.image_snapshot();
+            let mut document = new_document(Some(&metadata));
+            for _ in 0..4 {
+                let mut page = document.begin_page((100, 100), None);
+                page.canvas().draw_image(&image, (10, 10), None);
+                document = page.end_page();
+            }
+            let pdf = document.close();
+            assert!(pdf.as_bytes().starts_with(b"%PDF"));
+            assert!(jobs.load(Ordering::SeqCst) > 0);
+        }
+
+        // A font with TrueType outlines, which is embedded as a font file the PDF states the
+        // uncompressed size of.
+        fn true_type_font() -> Font {
+            let glyf = u32::from_be_bytes(*b"glyf");
+            let font_mgr = FontMgr::new();
+            let typeface = (0..font_mgr.count_families())
+                .flat_map(|family| {
+                    let mut style_set = font_mgr.new_styleset(family);
+                    (0..style_set.count()).filter_map(move |i| style_set.new_typeface(i))
+                })
+                .find(|typeface| typeface.table_size(glyf).is_some())
+                .expect("no font with TrueType outlines is installed");
+            Font::from_typeface(typeface, None)
+        }
+
+        fn font_file_size(font: &Font) -> usize {
+            let typeface = font.typeface_or_default();
+            typeface
+                .table_tags()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|tag| typeface.table_size(tag))
+                .sum()
+        }
+
+        fn embedded_font_file_size(subsetter: Subsetter, font: &Font) -> usize {
+            let metadata = Metadata {
+                subsetter,
+                ..Metadata::default()
+            };
+            let mut page = new_document(Some(&metadata)).begin_page((200, 100), None);
+            page.canvas()
+                .draw_str("Subsetting", (10, 50), font, &Paint::default());
+            let pdf = page.end_page().close();
+
+            let key = b"/Length1 ";
+            let pdf = pdf.as_bytes();
+            let i = pdf
+                .windows(key.len())
+                .position(|w| w == key)
+                .expect("the font file is not embedded");
+            let digits = pdf[i + key.len()..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .map(|b| *b as char)
+                .collect::<String>();
+            digits.parse().unwrap()
+        }
+
+        #[test]
+        #[cfg(feature = "shaper")]
+        fn each_subsetter_embeds_only_the_used_glyphs() {
+            let font = true_type_font();
+            let font_file_size = font_file_size(&font);
+            // Sfntly is not built, so HarfBuzz subsets the fonts in its place.
+            for subsetter in &[Subsetter::Harfbuzz, Subsetter::Sfntly] {
+                let embedded_size = embedded_font_file_size(*subsetter, &font);
+                assert!(embedded_size < font_file_size / 2);
+            }
+        }
+
+        #[test]
+        #[cfg(not(feature = "shaper"))]
+        fn fonts_are_embedded_completely_without_a_subsetter() {
+            let font = true_type_font();
+            let font_file_size = font_file_size(&font);
+            for subsetter in &[Subsetter::Harfbuzz, Subsetter::Sfntly] {
+                let embedded_size = embedded_font_file_size(*subsetter, &font);
+                // the font file also contains the table directory.
+                assert!(embedded_size >= font_file_size);
+            }
+        }
+    }
 }
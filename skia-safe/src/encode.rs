@@ -0,0 +1,252 @@
+use crate::interop::WriterStream;
+use crate::prelude::*;
+use crate::{Bitmap, Data, EncodedImageFormat, Image, Pixmap};
+use skia_bindings as sb;
+use skia_bindings::{SkEncoder, SkWStream};
+use std::io;
+use std::marker::PhantomData;
+
+pub mod jpeg;
+pub mod png;
+#[cfg(feature = "webp")]
+pub mod webp;
+
+pub fn pixmap(src: &Pixmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
+    Data::from_ptr(unsafe {
+        sb::C_SkEncodePixmap(
+            src.native(),
+            format.into_native(),
+            quality.try_into().unwrap(),
+        )
+    })
+}
+
+pub fn bitmap(src: &Bitmap, format: EncodedImageFormat, quality: usize) -> Option<Data> {
+    Data::from_ptr(unsafe {
+        sb::C_SkEncodeBitmap(
+            src.native(),
+            format.into_native(),
+            quality.try_into().unwrap(),
+        )
+    })
+}
+
+/// The options of a specific encoder, which select the format to encode to.
+///
+/// Implemented by [`png::Options`], [`jpeg::Options`] and `webp::Options`.
+pub trait EncoderOptions {
+    fn encode_pixmap(&self, src: &Pixmap) -> Option<Data>;
+
+    /// Encodes the pixels of `src` into `writer` without keeping the encoded image in memory.
+    ///
+    /// Returns the writer, or the first I/O error the writer returned.
+    fn encode_pixmap_to<W: io::Write>(&self, src: &Pixmap, writer: W) -> io::Result<W>;
+
+    fn encode_bitmap(&self, src: &Bitmap) -> Option<Data> {
+        self.encode_pixmap(&src.peek_pixels()?)
+    }
+
+    fn encode_bitmap_to<W: io::Write>(&self, src: &Bitmap, writer: W) -> io::Result<W> {
+        let pixmap = src.peek_pixels().ok_or_else(no_pixels)?;
+        self.encode_pixmap_to(&pixmap, writer)
+    }
+
+    fn encode_image(&self, src: &Image) -> Option<Data> {
+        let raster = src.new_raster_image()?;
+        self.encode_pixmap(&raster.peek_pixels()?)
+    }
+
+    fn encode_image_to<W: io::Write>(&self, src: &Image, writer: W) -> io::Result<W> {
+        let raster = src.new_raster_image().ok_or_else(no_pixels)?;
+        let pixmap = raster.peek_pixels().ok_or_else(no_pixels)?;
+        self.encode_pixmap_to(&pixmap, writer)
+    }
+}
+
+fn no_pixels() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "no pixels to encode")
+}
+
+pub(crate) fn encoding_failed() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "the pixels could not be encoded",
+    )
+}
+
+impl EncoderOptions for png::Options {
+    fn encode_pixmap(&self, src: &Pixmap) -> Option<Data> {
+        png::encode(src, self)
+    }
+
+    fn encode_pixmap_to<W: io::Write>(&self, src: &Pixmap, writer: W) -> io::Result<W> {
+        png::encoder(src, writer, self)?.finish()
+    }
+}
+
+impl EncoderOptions for jpeg::Options {
+    fn encode_pixmap(&self, src: &Pixmap) -> Option<Data> {
+        jpeg::encode(src, self)
+    }
+
+    fn encode_pixmap_to<W: io::Write>(&self, src: &Pixmap, writer: W) -> io::Result<W> {
+        jpeg::encoder(src, writer, self)?.finish()
+    }
+}
+
+#[cfg(feature = "webp")]
+impl EncoderOptions for webp::Options {
+    fn encode_pixmap(&self, src: &Pixmap) -> Option<Data> {
+        webp::encode(src, self)
+    }
+
+    fn encode_pixmap_to<W: io::Write>(&self, src: &Pixmap, writer: W) -> io::Result<W> {
+        webp::encode_to(src, writer, self)
+    }
+}
+
+impl NativeDrop for SkEncoder {
+    fn drop(&mut self) {
+        unsafe { sb::C_SkEncoder_delete(self) }
+    }
+}
+
+/// Encodes the rows of a pixmap step by step into a writer, see [`png::encoder()`] and
+/// [`jpeg::encoder()`].
+pub struct Encoder<'a, W: io::Write> {
+    // must be dropped before the stream it writes to.
+    native: RefHandle<SkEncoder>,
+    stream: Box<WriterStream<W>>,
+    remaining_rows: usize,
+    pd: PhantomData<&'a [u8]>,
+}
+
+impl<'a, W: io::Write> Encoder<'a, W> {
+    pub(crate) fn new(
+        src: &'a Pixmap,
+        writer: W,
+        make: impl FnOnce(*mut SkWStream) -> *mut SkEncoder,
+    ) -> io::Result<Self> {
+        // the stream is boxed, because the native encoder keeps a pointer to it.
+        let mut stream = Box::new(WriterStream::new(writer));
+        match RefHandle::from_ptr(make(stream.stream_mut() as *mut _)) {
+            Some(native) => Ok(Encoder {
+                native,
+                stream,
+                remaining_rows: src.height().try_into().unwrap(),
+                pd: PhantomData,
+            }),
+            None => Err(stream.take_error().unwrap_or_else(encoding_failed)),
+        }
+    }
+
+    /// The number of rows that are not encoded yet.
+    pub fn remaining_rows(&self) -> usize {
+        self.remaining_rows
+    }
+
+    /// Encodes the next `num_rows` rows and writes the encoded data to the writer.
+    pub fn encode_rows(&mut self, num_rows: usize) -> io::Result<()> {
+        let num_rows = num_rows.min(self.remaining_rows);
+        if num_rows == 0 {
+            return Ok(());
+        }
+        if !unsafe {
+            sb::C_SkEncoder_encodeRows(self.native.native_mut(), num_rows.try_into().unwrap())
+        } {
+            self.remaining_rows = 0;
+            return Err(self.stream.take_error().unwrap_or_else(encoding_failed));
+        }
+        self.remaining_rows -= num_rows;
+        Ok(())
+    }
+
+    /// Encodes all remaining rows and returns the flushed writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.encode_rows(self.remaining_rows)?;
+        let Encoder { native, stream, .. } = self;
+        drop(native);
+        stream.into_writer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jpeg, png};
+    use crate::{Color, ColorSpace, EncodedImageFormat, Image, ImageInfo, Surface};
+
+    /// A 64x64 sRGB image with color gradients in both directions, which compresses
+    /// differently depending on the encoder options.
+    pub(crate) fn gradient_image() -> Image {
+        let info = ImageInfo::new_n32_premul((64, 64), ColorSpace::new_srgb());
+        let mut surface = Surface::new_raster(&info, None, None).unwrap();
+        let canvas = surface.canvas();
+        for i in 0..64u8 {
+            let mut paint = crate::Paint::default();
+            paint.set_color(Color::from_rgb(i * 4, 255 - i * 4, i.wrapping_mul(16)));
+            canvas.draw_rect(
+                crate::Rect::new(i.into(), 0.0, 64.0, 64.0 - f32::from(i)),
+                &paint,
+            );
+        }
+        surface.image_snapshot()
+    }
+
+    fn image() -> crate::Image {
+        let mut surface = Surface::new_raster_n32_premul((32, 32)).unwrap();
+        let canvas = surface.canvas();
+        canvas.clear(Color::WHITE);
+        let mut paint = crate::Paint::default();
+        paint.set_color(Color::MAGENTA).set_anti_alias(true);
+        canvas.draw_circle((16.0, 16.0), 12.0, &paint);
+        surface.image_snapshot()
+    }
+
+    #[test]
+    fn image_encodes_with_options() {
+        let image = image();
+        let png = image.encode_with(&png::Options::default()).unwrap();
+        assert!(png.as_bytes().starts_with(b"\x89PNG"));
+
+        let best = image.encode_with(&jpeg::Options::default()).unwrap();
+        let low = image
+            .encode_with(&jpeg::Options {
+                quality: 20,
+                ..jpeg::Options::default()
+            })
+            .unwrap();
+        assert!(low.size() < best.size());
+    }
+
+    #[test]
+    fn image_encodes_to_data_with_quality() {
+        let image = image();
+        let best = image.encode_to_data(EncodedImageFormat::JPEG).unwrap();
+        let low = image
+            .encode_to_data_with_quality(EncodedImageFormat::JPEG, 20)
+            .unwrap();
+        assert!(low.size() < best.size());
+    }
+
+    #[test]
+    fn pixmap_and_bitmap_encode_with_options() {
+        let image = image();
+        let pixmap = image.peek_pixels().unwrap();
+        assert!(pixmap.encode_with(&png::Options::default()).is_some());
+        let mut bitmap = crate::Bitmap::new();
+        bitmap.alloc_n32_pixels((32, 32), None);
+        bitmap.erase_color(Color::CYAN);
+        assert!(bitmap.encode_with(&jpeg::Options::default()).is_some());
+    }
+
+    #[test]
+    fn image_encodes_to_writer() {
+        let image = image();
+        let options = png::Options::default();
+        let written = image.encode_to(Vec::new(), &options).unwrap();
+        assert_eq!(
+            image.encode_with(&options).unwrap().as_bytes(),
+            written.as_slice()
+        );
+    }
+}
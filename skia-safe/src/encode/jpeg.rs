@@ -0,0 +1,206 @@
+use crate::encode::Encoder;
+use crate::interop::DynamicMemoryWStream;
+use crate::prelude::*;
+use crate::{Data, Pixmap};
+use skia_bindings as sb;
+use skia_bindings::{SkJpegEncoder_AlphaOption, SkJpegEncoder_Downsample};
+use std::io;
+
+/// The chroma subsampling the encoder uses.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum Downsample {
+    /// Reduces the horizontal and vertical resolution of the chroma channels by half.
+    BothDirections = SkJpegEncoder_Downsample::k420 as _,
+    /// Reduces the horizontal resolution of the chroma channels by half.
+    Horizontal = SkJpegEncoder_Downsample::k422 as _,
+    /// Keeps the full resolution of the chroma channels.
+    No = SkJpegEncoder_Downsample::k444 as _,
+}
+
+impl NativeTransmutable<SkJpegEncoder_Downsample> for Downsample {}
+#[test]
+fn test_downsample_layout() {
+    Downsample::test_layout()
+}
+
+impl Default for Downsample {
+    fn default() -> Self {
+        Downsample::BothDirections
+    }
+}
+
+/// How the encoder handles transparent pixels, because JPEG images are always opaque.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum AlphaOption {
+    Ignore = SkJpegEncoder_AlphaOption::kIgnore as _,
+    BlendOnBlack = SkJpegEncoder_AlphaOption::kBlendOnBlack as _,
+}
+
+impl NativeTransmutable<SkJpegEncoder_AlphaOption> for AlphaOption {}
+#[test]
+fn test_alpha_option_layout() {
+    AlphaOption::test_layout()
+}
+
+impl Default for AlphaOption {
+    fn default() -> Self {
+        AlphaOption::Ignore
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Options {
+    /// The quality from 0 to 100, where 100 produces the highest quality and the largest size.
+    /// Higher values are treated as 100.
+    pub quality: usize,
+    pub downsample: Downsample,
+    pub alpha_option: AlphaOption,
+    /// Embeds the ICC profile of the pixmap's color space if there is one.
+    pub embed_icc_profile: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            quality: 100,
+            downsample: Downsample::default(),
+            alpha_option: AlphaOption::default(),
+            embed_icc_profile: true,
+        }
+    }
+}
+
+/// Encodes the pixels of `src` as a JPEG image.
+///
+/// Returns `None` if the pixels could not be encoded, for example if the color type is not
+/// supported.
+pub fn encode(src: &Pixmap, options: &Options) -> Option<Data> {
+    with_profile_option(src, options, |src| {
+        let mut stream = DynamicMemoryWStream::new();
+        unsafe {
+            sb::C_SkJpegEncoder_Encode(
+                stream.native_mut().base_mut(),
+                src.native(),
+                quality(options),
+                options.downsample.into_native(),
+                options.alpha_option.into_native(),
+            )
+        }
+        .if_true_then_some(|| stream.detach_as_data())
+    })
+}
+
+/// Creates an encoder that encodes the pixels of `src` row by row as a JPEG image into
+/// `writer`.
+///
+/// Returns an error if the pixels can not be encoded, for example if the color type is not
+/// supported.
+pub fn encoder<'a, W: io::Write>(
+    src: &'a Pixmap,
+    writer: W,
+    options: &Options,
+) -> io::Result<Encoder<'a, W>> {
+    // the native encoder copies the pixmap, so it is fine if it refers to a temporary one.
+    with_profile_option(src, options, |pixmap| {
+        Encoder::new(src, writer, |dst| unsafe {
+            sb::C_SkJpegEncoder_Make(
+                dst,
+                pixmap.native(),
+                quality(options),
+                options.downsample.into_native(),
+                options.alpha_option.into_native(),
+            )
+        })
+    })
+}
+
+fn quality(options: &Options) -> i32 {
+    options.quality.min(100) as i32
+}
+
+fn with_profile_option<R>(src: &Pixmap, options: &Options, f: impl FnOnce(&Pixmap) -> R) -> R {
+    if options.embed_icc_profile || src.color_space().is_none() {
+        f(src)
+    } else {
+        // The encoder embeds the profile of the color space, so we refer to the same pixels
+        // without one.
+        let info = src.info().with_color_space(None);
+        let pixels = unsafe {
+            std::slice::from_raw_parts(
+                src.addr() as *const u8,
+                info.compute_byte_size(src.row_bytes()),
+            )
+        };
+        f(&Pixmap::new(&info, pixels, src.row_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, encoder, Downsample, Options};
+    use crate::encode::tests::gradient_image;
+    use crate::{Color, ImageInfo, Surface};
+
+    fn encode_gradient(options: &Options) -> crate::Data {
+        encode(&gradient_image().peek_pixels().unwrap(), options).unwrap()
+    }
+
+    #[test]
+    fn full_chroma_resolution_increases_size() {
+        let subsampled = encode_gradient(&Options::default());
+        let full = encode_gradient(&Options {
+            downsample: Downsample::No,
+            ..Options::default()
+        });
+        assert!(subsampled.size() < full.size());
+    }
+
+    #[test]
+    fn icc_profile_can_be_omitted() {
+        let with_profile = encode_gradient(&Options::default());
+        let without_profile = encode_gradient(&Options {
+            embed_icc_profile: false,
+            ..Options::default()
+        });
+        let icc_marker = b"ICC_PROFILE\0";
+        let contains_marker = |data: &crate::Data| {
+            data.as_bytes()
+                .windows(icc_marker.len())
+                .any(|w| w == icc_marker)
+        };
+        assert!(contains_marker(&with_profile));
+        assert!(!contains_marker(&without_profile));
+    }
+
+    #[test]
+    fn quality_above_100_is_clamped() {
+        let clamped = encode_gradient(&Options {
+            quality: 1000,
+            ..Options::default()
+        });
+        assert_eq!(
+            encode_gradient(&Options::default()).as_bytes(),
+            clamped.as_bytes()
+        );
+    }
+
+    #[test]
+    fn encoder_writes_to_writer() {
+        let info = ImageInfo::new_n32_premul((16, 16), None);
+        let mut surface = Surface::new_raster(&info, None, None).unwrap();
+        surface.canvas().clear(Color::RED);
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+        let options = Options::default();
+        let mut encoder = encoder(&pixmap, Vec::new(), &options).unwrap();
+        encoder.encode_rows(5).unwrap();
+        assert_eq!(11, encoder.remaining_rows());
+        let written = encoder.finish().unwrap();
+        assert_eq!(
+            encode(&pixmap, &options).unwrap().as_bytes(),
+            written.as_slice()
+        );
+    }
+}
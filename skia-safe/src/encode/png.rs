@@ -0,0 +1,193 @@
+use crate::encode::Encoder;
+use crate::interop::DynamicMemoryWStream;
+use crate::prelude::*;
+use crate::{Data, Pixmap};
+use skia_bindings as sb;
+use skia_bindings::SkPngEncoder_FilterFlag;
+use std::ffi::CString;
+use std::io;
+use std::os::raw::c_char;
+
+bitflags! {
+    /// The row filters the encoder chooses from, see the PNG specification.
+    pub struct FilterFlag: i32 {
+        const ZERO = SkPngEncoder_FilterFlag::kZero as _;
+        const NONE = SkPngEncoder_FilterFlag::kNone as _;
+        const SUB = SkPngEncoder_FilterFlag::kSub as _;
+        const UP = SkPngEncoder_FilterFlag::kUp as _;
+        const AVG = SkPngEncoder_FilterFlag::kAvg as _;
+        const PAETH = SkPngEncoder_FilterFlag::kPaeth as _;
+        const ALL = SkPngEncoder_FilterFlag::kAll as _;
+    }
+}
+
+impl Default for FilterFlag {
+    fn default() -> Self {
+        FilterFlag::ALL
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Options {
+    /// Selects the filters the encoder may use for each row. Using fewer filters speeds up
+    /// encoding, but usually increases the size.
+    pub filter_flags: FilterFlag,
+    /// The zlib compression level from 0 to 9, where 9 produces the smallest size and takes
+    /// the longest time. Higher levels are treated as 9.
+    pub zlib_level: usize,
+    /// Keyword and text pairs that are stored in tEXt chunks. They must not contain NUL
+    /// characters.
+    pub comments: Vec<(String, String)>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            filter_flags: FilterFlag::default(),
+            zlib_level: 6,
+            comments: Vec::new(),
+        }
+    }
+}
+
+/// Encodes the pixels of `src` as a PNG image.
+///
+/// Returns `None` if the pixels could not be encoded, for example if the color type is not
+/// supported, or if a comment contains a NUL character.
+pub fn encode(src: &Pixmap, options: &Options) -> Option<Data> {
+    let (_comments, comments_ptrs) = comments(options)?;
+    let mut stream = DynamicMemoryWStream::new();
+    unsafe {
+        sb::C_SkPngEncoder_Encode(
+            stream.native_mut().base_mut(),
+            src.native(),
+            options.filter_flags.bits(),
+            zlib_level(options),
+            comments_ptrs.as_ptr(),
+            comments_ptrs.len(),
+        )
+    }
+    .if_true_then_some(|| stream.detach_as_data())
+}
+
+/// Creates an encoder that encodes the pixels of `src` row by row as a PNG image into `writer`.
+///
+/// Returns an error if the pixels can not be encoded, for example if the color type is not
+/// supported, or if a comment contains a NUL character.
+pub fn encoder<'a, W: io::Write>(
+    src: &'a Pixmap,
+    writer: W,
+    options: &Options,
+) -> io::Result<Encoder<'a, W>> {
+    let (_comments, comments_ptrs) = comments(options).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a comment contains a NUL character",
+        )
+    })?;
+    Encoder::new(src, writer, |dst| unsafe {
+        sb::C_SkPngEncoder_Make(
+            dst,
+            src.native(),
+            options.filter_flags.bits(),
+            zlib_level(options),
+            comments_ptrs.as_ptr(),
+            comments_ptrs.len(),
+        )
+    })
+}
+
+fn zlib_level(options: &Options) -> i32 {
+    options.zlib_level.min(9) as i32
+}
+
+// The returned pointers refer to the returned strings.
+fn comments(options: &Options) -> Option<(Vec<CString>, Vec<*const c_char>)> {
+    let comments = options
+        .comments
+        .iter()
+        .flat_map(|(keyword, text)| vec![keyword, text])
+        .map(|str| CString::new(str.as_str()).ok())
+        .collect::<Option<Vec<CString>>>()?;
+    let comments_ptrs = comments.iter().map(|str| str.as_ptr()).collect();
+    Some((comments, comments_ptrs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, encoder, FilterFlag, Options};
+    use crate::encode::tests::gradient_image;
+    use crate::interop::FailingWriter;
+    use std::io;
+
+    fn pixmap_encode(options: &Options) -> crate::Data {
+        encode(&gradient_image().peek_pixels().unwrap(), options).unwrap()
+    }
+
+    #[test]
+    fn zlib_level_affects_size() {
+        let fast = pixmap_encode(&Options {
+            filter_flags: FilterFlag::NONE,
+            zlib_level: 0,
+            ..Options::default()
+        });
+        let small = pixmap_encode(&Options {
+            zlib_level: 9,
+            ..Options::default()
+        });
+        assert!(small.size() < fast.size());
+    }
+
+    #[test]
+    fn comments_are_stored_in_text_chunks() {
+        let data = pixmap_encode(&Options {
+            comments: vec![("Title".into(), "Gradient".into())],
+            ..Options::default()
+        });
+        let text_chunk = b"tEXtTitle\0Gradient";
+        assert!(data
+            .as_bytes()
+            .windows(text_chunk.len())
+            .any(|w| w == text_chunk));
+    }
+
+    #[test]
+    fn comments_with_nul_characters_are_rejected() {
+        let image = gradient_image();
+        let pixmap = image.peek_pixels().unwrap();
+        let options = Options {
+            comments: vec![("Title".into(), "Gra\0dient".into())],
+            ..Options::default()
+        };
+        assert!(encode(&pixmap, &options).is_none());
+        let error = encoder(&pixmap, Vec::new(), &options).err().unwrap();
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+    }
+
+    #[test]
+    fn encoder_writes_rows_to_writer() {
+        let image = gradient_image();
+        let pixmap = image.peek_pixels().unwrap();
+        let options = Options::default();
+        let mut encoder = encoder(&pixmap, Vec::new(), &options).unwrap();
+        while encoder.remaining_rows() > 0 {
+            encoder.encode_rows(10).unwrap();
+        }
+        let written = encoder.finish().unwrap();
+        assert_eq!(
+            encode(&pixmap, &options).unwrap().as_bytes(),
+            written.as_slice()
+        );
+    }
+
+    #[test]
+    fn encoder_returns_the_writer_error() {
+        let image = gradient_image();
+        let pixmap = image.peek_pixels().unwrap();
+        let error = encoder(&pixmap, FailingWriter, &Options::default())
+            .and_then(|encoder| encoder.finish())
+            .err()
+            .unwrap();
+        assert_eq!("disk full", error.to_string());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::interop::{DynamicMemoryWStream, WriterStream};
+use crate::prelude::*;
+use crate::{Data, Pixmap};
+use skia_bindings as sb;
+use skia_bindings::{SkWStream, SkWebpEncoder_Compression};
+use std::io;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[repr(i32)]
+pub enum Compression {
+    Lossy = SkWebpEncoder_Compression::kLossy as _,
+    Lossless = SkWebpEncoder_Compression::kLossless as _,
+}
+
+impl NativeTransmutable<SkWebpEncoder_Compression> for Compression {}
+#[test]
+fn test_compression_layout() {
+    Compression::test_layout()
+}
+
+impl Default for Compression {
+    fn default() -> Self {
+        Compression::Lossy
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Options {
+    pub compression: Compression,
+    /// A value from 0 to 100. Values outside of this range are clamped.
+    ///
+    /// For lossy compression, this is the visual quality, where 100 produces the highest quality
+    /// and the largest size. For lossless compression, this is the effort spent to reduce the
+    /// size, where 100 produces the smallest size and takes the longest time.
+    pub quality: f32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            compression: Compression::default(),
+            quality: 100.0,
+        }
+    }
+}
+
+/// Encodes the pixels of `src` as a WebP image.
+///
+/// Returns `None` if the pixels could not be encoded, for example if the color type is not
+/// supported.
+pub fn encode(src: &Pixmap, options: &Options) -> Option<Data> {
+    let mut stream = DynamicMemoryWStream::new();
+    encode_to_stream(stream.native_mut().base_mut(), src, options)
+        .if_true_then_some(|| stream.detach_as_data())
+}
+
+/// Encodes the pixels of `src` as a WebP image into `writer`.
+///
+/// The WebP encoder does not support encoding row by row, so the whole image is encoded at once.
+pub fn encode_to<W: io::Write>(src: &Pixmap, writer: W, options: &Options) -> io::Result<W> {
+    let mut stream = WriterStream::new(writer);
+    if !encode_to_stream(stream.stream_mut(), src, options) {
+        return Err(stream
+            .take_error()
+            .unwrap_or_else(crate::encode::encoding_failed));
+    }
+    stream.into_writer()
+}
+
+fn encode_to_stream(dst: &mut SkWStream, src: &Pixmap, options: &Options) -> bool {
+    unsafe {
+        sb::C_SkWebpEncoder_Encode(
+            dst,
+            src.native(),
+            options.compression.into_native(),
+            options.quality.max(0.0).min(100.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode, Compression, Options};
+    use crate::encode::tests::gradient_image;
+    use crate::{Codec, Color, EncodedImageFormat, Surface};
+
+    #[test]
+    fn encode_lossless_roundtrip() {
+        let mut surface = Surface::new_raster_n32_premul((32, 32)).unwrap();
+        surface.canvas().clear(Color::GREEN);
+        let image = surface.image_snapshot();
+        let pixmap = image.peek_pixels().unwrap();
+
+        let options = Options {
+            compression: Compression::Lossless,
+            ..Options::default()
+        };
+        let data = encode(&pixmap, &options).unwrap();
+        let mut codec = Codec::from_data(data).unwrap();
+        assert_eq!(EncodedImageFormat::WEBP, codec.encoded_format());
+        let decoded = codec.decode_image(false).unwrap();
+        assert_eq!(
+            Color::GREEN,
+            decoded.peek_pixels().unwrap().get_color((16, 16))
+        );
+    }
+
+    #[test]
+    fn lower_quality_leads_to_smaller_size() {
+        let image = gradient_image();
+        let pixmap = image.peek_pixels().unwrap();
+
+        let high = encode(&pixmap, &Options::default()).unwrap();
+        let low = encode(
+            &pixmap,
+            &Options {
+                quality: 10.0,
+                ..Options::default()
+            },
+        )
+        .unwrap();
+        assert!(low.size() < high.size());
+    }
+}
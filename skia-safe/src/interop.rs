@@ -1,6 +1,10 @@
 /// Simple Skia types that are not exported and used to
 /// to marshal between Rust and Skia types only.
+///
+/// The exception is the `WriterStream`, which appears in the types of documents and
+/// canvases that write to a `std::io::Write`.
 mod stream;
+pub use self::stream::WriterStream;
 pub(crate) use self::stream::*;
 
 mod string;
@@ -6,9 +6,14 @@
 use crate::prelude::*;
 use crate::Data;
 use skia_bindings as sb;
-use skia_bindings::{SkDynamicMemoryWStream, SkMemoryStream, SkStream, SkStreamAsset, SkWStream};
+use skia_bindings::{
+    RustStreamAsset_Param, RustStream_Param, RustWStream, RustWStream_Param,
+    SkDynamicMemoryWStream, SkMemoryStream, SkStream, SkStreamAsset, SkWStream,
+};
+use std::ffi::c_void;
 use std::marker::PhantomData;
-use std::ptr;
+use std::sync::{Arc, Mutex};
+use std::{io, mem, ptr, slice};
 
 /// Trait representing an Skia allocated Stream type with a base class of SkStream.
 #[repr(transparent)]
@@ -32,6 +37,146 @@ impl<N: NativeStreamBase> Stream<N> {
         assert_ne!(ptr, ptr::null_mut());
         Stream(ptr)
     }
+
+    /// Releases the ownership of the native stream, for passing it to functions that consume it.
+    pub fn into_ptr(self) -> *mut N {
+        let ptr = self.0;
+        mem::forget(self);
+        ptr
+    }
+}
+
+impl<N: NativeStreamBase> NativeAccess<N> for Stream<N> {
+    fn native(&self) -> &N {
+        unsafe { &*self.0 }
+    }
+    fn native_mut(&mut self) -> &mut N {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl NativeStreamBase for SkStream {
+    fn as_stream_mut(&mut self) -> &mut SkStream {
+        self
+    }
+}
+
+impl Stream<SkStream> {
+    /// Creates a native stream that reads from `reader`.
+    ///
+    /// The stream does not support rewinding or seeking. If the reader returns no bytes, the
+    /// stream reports its end, but may continue to read if the reader provides more bytes later.
+    pub fn from_read<R: io::Read + Send + 'static>(reader: R) -> Stream<SkStream> {
+        let state = Box::new(ReadState {
+            reader,
+            buffer: Vec::new(),
+            at_end: false,
+        });
+        let param = RustStream_Param {
+            data: Box::into_raw(state) as _,
+            read: Some(read_state_read::<R>),
+            peek: Some(read_state_peek::<R>),
+            isAtEnd: Some(read_state_is_at_end::<R>),
+            drop: Some(read_state_drop::<R>),
+        };
+        Stream::from_ptr(unsafe { sb::C_RustStream_new(&param) })
+    }
+}
+
+// The state of a native stream that reads from a Rust reader. Bytes that were peeked are
+// kept in the buffer until they are read.
+struct ReadState<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    at_end: bool,
+}
+
+impl<R: io::Read> ReadState<R> {
+    // The maximum number of bytes that are buffered at once.
+    const CHUNK_SIZE: usize = 0x10000;
+
+    // Tries to buffer `size` bytes and returns the number of bytes available.
+    fn fill(&mut self, size: usize) -> usize {
+        while self.buffer.len() < size {
+            let len = self.buffer.len();
+            self.buffer.resize(size, 0);
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(0) => {
+                    self.buffer.truncate(len);
+                    self.at_end = true;
+                    break;
+                }
+                Ok(read) => {
+                    self.buffer.truncate(len + read);
+                    self.at_end = false;
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => self.buffer.truncate(len),
+                Err(_) => {
+                    self.buffer.truncate(len);
+                    self.at_end = true;
+                    break;
+                }
+            }
+        }
+        self.buffer.len().min(size)
+    }
+
+    fn read(&mut self, mut dst: Option<&mut [u8]>, size: usize) -> usize {
+        let mut read = 0;
+        while read < size {
+            let available = self.fill((size - read).min(Self::CHUNK_SIZE));
+            if available == 0 {
+                break;
+            }
+            if let Some(dst) = dst.as_mut() {
+                dst[read..read + available].copy_from_slice(&self.buffer[..available]);
+            }
+            self.buffer.drain(..available);
+            read += available;
+        }
+        read
+    }
+
+    fn peek(&mut self, dst: &mut [u8]) -> usize {
+        let available = self.fill(dst.len());
+        dst[..available].copy_from_slice(&self.buffer[..available]);
+        available
+    }
+}
+
+extern "C" fn read_state_read<R: io::Read>(
+    data: *mut c_void,
+    dst: *mut c_void,
+    size: usize,
+) -> usize {
+    let state = unsafe { &mut *(data as *mut ReadState<R>) };
+    if dst.is_null() {
+        // SkStream::skip()
+        state.read(None, size)
+    } else {
+        state.read(
+            Some(unsafe { slice::from_raw_parts_mut(dst as *mut u8, size) }),
+            size,
+        )
+    }
+}
+
+extern "C" fn read_state_peek<R: io::Read>(
+    data: *mut c_void,
+    dst: *mut c_void,
+    size: usize,
+) -> usize {
+    let state = unsafe { &mut *(data as *mut ReadState<R>) };
+    state.peek(unsafe { slice::from_raw_parts_mut(dst as *mut u8, size) })
+}
+
+extern "C" fn read_state_is_at_end<R: io::Read>(data: *mut c_void) -> bool {
+    let state = unsafe { &*(data as *mut ReadState<R>) };
+    state.buffer.is_empty() && state.at_end
+}
+
+extern "C" fn read_state_drop<R: io::Read>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut ReadState<R>) })
 }
 
 pub type StreamAsset = Stream<SkStreamAsset>;
@@ -43,15 +188,143 @@ impl NativeStreamBase for SkStreamAsset {
     }
 }
 
-impl NativeAccess<SkStreamAsset> for Stream<SkStreamAsset> {
-    fn native(&self) -> &SkStreamAsset {
-        unsafe { &*self.0 }
+impl Stream<SkStreamAsset> {
+    /// Creates a native stream asset that reads the data from the start to the end of `reader`.
+    ///
+    /// The stream supports rewinding, seeking and duplication. Duplicates share the reader and
+    /// seek it to their own position before they read from it.
+    ///
+    /// Returns an error if the reader can not be seeked, or if its length exceeds `usize`.
+    pub fn from_read_seek<R: io::Read + io::Seek + Send + 'static>(
+        mut reader: R,
+    ) -> io::Result<StreamAsset> {
+        let length = reader.seek(io::SeekFrom::End(0))?;
+        if usize::try_from(length).is_err() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the reader is too long for a native stream",
+            ));
+        }
+        let state = Box::new(SeekState {
+            shared: Arc::new(Mutex::new(SharedReader {
+                reader,
+                position: length,
+            })),
+            length,
+            position: 0,
+        });
+        let param = RustStreamAsset_Param {
+            data: Box::into_raw(state) as _,
+            read: Some(seek_state_read::<R>),
+            seek: Some(seek_state_seek::<R>),
+            getPosition: Some(seek_state_get_position::<R>),
+            getLength: Some(seek_state_get_length::<R>),
+            duplicate: Some(seek_state_duplicate::<R>),
+            drop: Some(seek_state_drop::<R>),
+        };
+        Ok(StreamAsset::from_ptr(unsafe {
+            sb::C_RustStreamAsset_new(&param)
+        }))
     }
-    fn native_mut(&mut self) -> &mut SkStreamAsset {
-        unsafe { &mut *self.0 }
+}
+
+// The state of a native stream asset that reads from a Rust reader. The reader is shared with
+// the duplicates of the stream, so its position is tracked to avoid unnecessary seeks.
+// The length fits into a usize and the position never exceeds it.
+struct SeekState<R> {
+    shared: Arc<Mutex<SharedReader<R>>>,
+    length: u64,
+    position: u64,
+}
+
+struct SharedReader<R> {
+    reader: R,
+    position: u64,
+}
+
+impl<R: io::Read + io::Seek> SeekState<R> {
+    fn read(&mut self, dst: Option<&mut [u8]>, size: usize) -> usize {
+        let size = size.min((self.length - self.position) as usize);
+        let dst = match dst {
+            Some(dst) => dst,
+            None => {
+                // skipping does not need to read.
+                self.position += size as u64;
+                return size;
+            }
+        };
+        // a reader that panicked while it was read from may be in any state.
+        let mut shared = match self.shared.lock() {
+            Ok(shared) => shared,
+            Err(_) => return 0,
+        };
+        if shared.position != self.position {
+            match shared.reader.seek(io::SeekFrom::Start(self.position)) {
+                Ok(position) => shared.position = position,
+                Err(_) => return 0,
+            }
+        }
+        let mut read = 0;
+        while read < size {
+            match shared.reader.read(&mut dst[read..size]) {
+                Ok(0) => break,
+                Ok(n) => read += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+        shared.position += read as u64;
+        self.position += read as u64;
+        read
+    }
+}
+
+extern "C" fn seek_state_read<R: io::Read + io::Seek>(
+    data: *mut c_void,
+    dst: *mut c_void,
+    size: usize,
+) -> usize {
+    let state = unsafe { &mut *(data as *mut SeekState<R>) };
+    if dst.is_null() {
+        // SkStream::skip()
+        state.read(None, size)
+    } else {
+        state.read(
+            Some(unsafe { slice::from_raw_parts_mut(dst as *mut u8, size) }),
+            size,
+        )
     }
 }
 
+extern "C" fn seek_state_seek<R>(data: *mut c_void, position: usize) -> bool {
+    let state = unsafe { &mut *(data as *mut SeekState<R>) };
+    state.position = (position as u64).min(state.length);
+    true
+}
+
+extern "C" fn seek_state_get_position<R>(data: *mut c_void) -> usize {
+    let state = unsafe { &*(data as *const SeekState<R>) };
+    state.position as usize
+}
+
+extern "C" fn seek_state_get_length<R>(data: *mut c_void) -> usize {
+    let state = unsafe { &*(data as *const SeekState<R>) };
+    state.length as usize
+}
+
+extern "C" fn seek_state_duplicate<R>(data: *mut c_void) -> *mut c_void {
+    let state = unsafe { &*(data as *const SeekState<R>) };
+    Box::into_raw(Box::new(SeekState {
+        shared: state.shared.clone(),
+        length: state.length,
+        position: 0,
+    })) as _
+}
+
+extern "C" fn seek_state_drop<R>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut SeekState<R>) })
+}
+
 #[repr(C)]
 pub struct MemoryStream<'a> {
     native: *mut SkMemoryStream,
@@ -132,6 +405,110 @@ impl Handle<SkDynamicMemoryWStream> {
     }
 }
 
+impl NativeBase<SkWStream> for RustWStream {}
+
+impl NativeDrop for RustWStream {
+    fn drop(&mut self) {
+        unsafe {
+            sb::C_SkWStream_destruct(self.base_mut());
+        }
+    }
+}
+
+/// A native write stream that writes to a Rust writer.
+///
+/// The first I/O error the writer returns is kept and causes all further writes to fail.
+pub struct WriterStream<W: io::Write> {
+    // must be dropped before the state it refers to.
+    native: Handle<RustWStream>,
+    state: Box<WriteState<W>>,
+}
+
+struct WriteState<W> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write> WriterStream<W> {
+    pub fn new(writer: W) -> WriterStream<W> {
+        let mut state = Box::new(WriteState {
+            writer,
+            error: None,
+        });
+        let param = RustWStream_Param {
+            data: state.as_mut() as *mut WriteState<W> as _,
+            write: Some(write_state_write::<W>),
+            flush: Some(write_state_flush::<W>),
+        };
+        let native =
+            Handle::construct(|stream| unsafe { sb::C_RustWStream_construct(stream, &param) });
+        WriterStream { native, state }
+    }
+
+    pub(crate) fn stream_mut(&mut self) -> &mut SkWStream {
+        self.native.native_mut().base_mut()
+    }
+
+    /// Returns the I/O error that caused a write to fail.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.state.error.take()
+    }
+
+    /// Flushes the writer and returns it, or the first I/O error that occurred.
+    pub fn into_writer(self) -> io::Result<W> {
+        let WriterStream { native, state } = self;
+        drop(native);
+        let WriteState { mut writer, error } = *state;
+        match error {
+            Some(error) => Err(error),
+            None => writer.flush().map(|_| writer),
+        }
+    }
+}
+
+extern "C" fn write_state_write<W: io::Write>(
+    data: *mut c_void,
+    buffer: *const c_void,
+    size: usize,
+) -> bool {
+    let state = unsafe { &mut *(data as *mut WriteState<W>) };
+    if state.error.is_some() {
+        return false;
+    }
+    let buffer = unsafe { slice::from_raw_parts(buffer as *const u8, size) };
+    match state.writer.write_all(buffer) {
+        Ok(()) => true,
+        Err(error) => {
+            state.error = Some(error);
+            false
+        }
+    }
+}
+
+extern "C" fn write_state_flush<W: io::Write>(data: *mut c_void) {
+    let state = unsafe { &mut *(data as *mut WriteState<W>) };
+    if state.error.is_none() {
+        if let Err(error) = state.writer.flush() {
+            state.error = Some(error);
+        }
+    }
+}
+
+/// A writer that fails to write, for testing how the errors of a [`WriterStream`] are reported.
+#[cfg(test)]
+pub(crate) struct FailingWriter;
+
+#[cfg(test)]
+impl io::Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[test]
 fn detaching_empty_dynamic_memory_w_stream_leads_to_non_null_data() {
     let mut stream = DynamicMemoryWStream::new();
@@ -144,3 +521,82 @@ fn memory_stream_from_bytes() {
     let stream = MemoryStream::from_bytes(&[1, 2, 3]);
     drop(stream);
 }
+
+#[test]
+fn read_state_peeks_without_consuming() {
+    let mut state = ReadState {
+        reader: io::Cursor::new(vec![1u8, 2, 3, 4, 5]),
+        buffer: Vec::new(),
+        at_end: false,
+    };
+    let mut peeked = [0u8; 2];
+    assert_eq!(2, state.peek(&mut peeked));
+    assert_eq!([1, 2], peeked);
+    let mut read = [0u8; 8];
+    assert_eq!(5, state.read(Some(&mut read), 8));
+    assert_eq!([1, 2, 3, 4, 5], read[..5]);
+    assert!(state.buffer.is_empty() && state.at_end);
+}
+
+#[test]
+fn writer_stream_writes_to_writer() {
+    let mut stream = WriterStream::new(Vec::new());
+    assert!(unsafe { sb::C_SkWStream_write(stream.stream_mut(), b"abc".as_ptr() as _, 3) });
+    assert_eq!(b"abc", stream.into_writer().unwrap().as_slice());
+}
+
+#[test]
+fn writer_stream_returns_the_writer_error() {
+    let mut stream = WriterStream::new(FailingWriter);
+    assert!(!unsafe { sb::C_SkWStream_write(stream.stream_mut(), b"abc".as_ptr() as _, 3) });
+    assert_eq!("disk full", stream.into_writer().err().unwrap().to_string());
+}
+
+#[test]
+fn seek_state_duplicates_keep_their_position() {
+    let shared = Arc::new(Mutex::new(SharedReader {
+        reader: io::Cursor::new(vec![1u8, 2, 3, 4, 5]),
+        position: 0,
+    }));
+    let mut state = SeekState {
+        shared: shared.clone(),
+        length: 5,
+        position: 0,
+    };
+    let mut duplicate = SeekState {
+        shared,
+        length: 5,
+        position: 0,
+    };
+    let mut buffer = [0u8; 2];
+    assert_eq!(2, state.read(Some(&mut buffer), 2));
+    assert_eq!([1, 2], buffer);
+    assert_eq!(2, duplicate.read(Some(&mut buffer), 2));
+    assert_eq!([1, 2], buffer);
+    assert_eq!(2, state.read(Some(&mut buffer), 2));
+    assert_eq!([3, 4], buffer);
+    assert_eq!(1, state.read(None, 8));
+    assert_eq!(5, state.position);
+}
+
+#[test]
+fn seek_state_does_not_read_from_a_poisoned_reader() {
+    let shared = Arc::new(Mutex::new(SharedReader {
+        reader: io::Cursor::new(vec![1u8, 2, 3]),
+        position: 0,
+    }));
+    let poison = shared.clone();
+    std::thread::spawn(move || {
+        let _shared = poison.lock().unwrap();
+        panic!("reader panicked");
+    })
+    .join()
+    .unwrap_err();
+    let mut state = SeekState {
+        shared,
+        length: 3,
+        position: 0,
+    };
+    let mut buffer = [0u8; 2];
+    assert_eq!(0, state.read(Some(&mut buffer), 2));
+}
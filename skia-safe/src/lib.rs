@@ -1,7 +1,9 @@
-mod codec;
+mod android;
+pub mod codec;
 mod core;
 mod docs;
 mod effects;
+pub mod encode;
 pub mod gpu;
 mod interop;
 mod modules;
@@ -21,8 +23,12 @@ extern crate lazy_static;
 // Prelude re-exports
 pub use crate::prelude::Borrows;
 
+// The stream documents, SVG canvases and encoders use to write to a `std::io::Write`.
+pub use crate::interop::WriterStream;
+
 /// All Sk* types are accessible via skia_safe::
-pub use crate::codec::*;
+pub use crate::android::*;
+pub use crate::codec::{codec_animation, AndroidCodec, AndroidOptions, Codec, EncodedOrigin};
 pub use crate::core::*;
 pub use crate::docs::*;
 pub use crate::effects::*;
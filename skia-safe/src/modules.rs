@@ -4,6 +4,10 @@ pub(crate) mod paragraph;
 pub mod shaper;
 #[cfg(feature = "shaper")]
 pub use shaper::{icu, Shaper};
+#[cfg(feature = "particles")]
+pub mod particles;
+#[cfg(feature = "animation")]
+pub mod skottie;
 
 // Export everything below paragraph under textlayout
 #[cfg(feature = "textlayout")]
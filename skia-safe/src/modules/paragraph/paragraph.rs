@@ -1,9 +1,12 @@
-use super::{PositionWithAffinity, RectHeightStyle, RectWidthStyle, TextBox};
+use super::{
+    Affinity, PositionWithAffinity, RectHeightStyle, RectWidthStyle, TextBox, TextDirection,
+};
 use crate::prelude::*;
 use crate::textlayout::LineMetrics;
-use crate::{scalar, Canvas, Point};
+use crate::{scalar, Canvas, Point, Rect};
 use skia_bindings as sb;
 use std::ops::{Index, Range};
+use std::{slice, str};
 
 pub type Paragraph = RefHandle<sb::skia_textlayout_Paragraph>;
 
@@ -109,6 +112,155 @@ impl RefHandle<sb::skia_textlayout_Paragraph> {
     pub fn mark_dirty(&mut self) {
         unsafe { sb::C_Paragraph_markDirty(self.native_mut()) }
     }
+
+    /// The text of the paragraph. All offsets the navigation functions take and return are
+    /// UTF-8 offsets into this text.
+    pub fn text(&self) -> &str {
+        unsafe {
+            let mut len = 0;
+            let ptr = sb::C_Paragraph_text(self.native(), &mut len);
+            if len == 0 {
+                return "";
+            }
+            str::from_utf8_unchecked(slice::from_raw_parts(ptr as *const u8, len))
+        }
+    }
+
+    /// Returns the offset of the grapheme boundary that follows `offset`, or the length of the
+    /// text if there is none.
+    pub fn next_grapheme_offset(&self, offset: usize) -> usize {
+        self.grapheme_boundaries(offset).end
+    }
+
+    /// Returns the offset of the grapheme boundary that precedes `offset`, or `0` if there is
+    /// none.
+    pub fn previous_grapheme_offset(&self, offset: usize) -> usize {
+        self.grapheme_boundaries(offset).start
+    }
+
+    /// Returns the index of the line the caret at `offset` is placed on.
+    ///
+    /// At a soft line break, an `Upstream` affinity selects the end of the preceding line and a
+    /// `Downstream` affinity the start of the following line. Returns `None` if the paragraph
+    /// is not laid out or `offset` is not inside the text.
+    pub fn get_line_number_at(&mut self, offset: usize, affinity: Affinity) -> Option<usize> {
+        line_number_at(self.get_line_metrics().as_slice(), offset, affinity)
+    }
+
+    /// Returns the text range of the line, from its start offset to its end offset, excluding
+    /// a trailing newline.
+    pub fn get_line_range(&mut self, line_number: usize) -> Option<Range<usize>> {
+        self.get_line_metrics()
+            .as_slice()
+            .get(line_number)
+            .map(|lm| lm.start_index..lm.end_index)
+    }
+
+    /// Returns the caret at `offset` as a zero width rectangle that spans the line's ascent and
+    /// descent, and the direction of the text the caret is placed in.
+    ///
+    /// In bidirectional text, the caret is placed at the leading edge of the grapheme that
+    /// follows `offset` on the line, or at the trailing edge of the grapheme that precedes it
+    /// at the end of a line.
+    pub fn get_caret(&mut self, offset: usize, affinity: Affinity) -> Option<TextBox> {
+        let (range, left, width, top, bottom) = {
+            let line_metrics = self.get_line_metrics();
+            let line_metrics = line_metrics.as_slice();
+            let lm = &line_metrics[line_number_at(line_metrics, offset, affinity)?];
+            (
+                lm.start_index..lm.end_index,
+                lm.left,
+                lm.width,
+                lm.baseline - lm.ascent,
+                lm.baseline + lm.descent,
+            )
+        };
+        let graphemes = self.grapheme_boundaries(offset);
+
+        let leading = if offset < range.end {
+            let next = graphemes.end.min(range.end);
+            self.grapheme_box(offset..next).map(|tb| match tb.direct {
+                TextDirection::LTR => (tb.rect.left, tb.direct),
+                TextDirection::RTL => (tb.rect.right, tb.direct),
+            })
+        } else {
+            None
+        };
+
+        let (x, direct) = leading
+            .or_else(|| {
+                if offset > range.start {
+                    let previous = graphemes.start.max(range.start);
+                    self.grapheme_box(previous..offset)
+                        .map(|tb| match tb.direct {
+                            TextDirection::LTR => (tb.rect.right, tb.direct),
+                            TextDirection::RTL => (tb.rect.left, tb.direct),
+                        })
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| {
+                // no glyphs to place the caret at, empty lines for example.
+                let x = if offset > range.start {
+                    left + width
+                } else {
+                    left
+                };
+                (x as scalar, TextDirection::LTR)
+            });
+
+        Some(TextBox {
+            rect: Rect::new(x, top as scalar, x, bottom as scalar),
+            direct,
+        })
+    }
+
+    /// The grapheme boundaries that precede and follow `offset`.
+    fn grapheme_boundaries(&self, offset: usize) -> Range<usize> {
+        let mut range: [usize; 2] = Default::default();
+        unsafe { sb::C_Paragraph_getGraphemeBoundaries(self.native(), offset, range.as_mut_ptr()) }
+        range[0]..range[1]
+    }
+
+    fn grapheme_box(&mut self, range: Range<usize>) -> Option<TextBox> {
+        if range.start >= range.end {
+            return None;
+        }
+        self.get_rects_for_range(range, RectHeightStyle::Tight, RectWidthStyle::Tight)
+            .iter()
+            .next()
+            .copied()
+    }
+}
+
+fn line_number_at(
+    line_metrics: &[LineMetrics],
+    offset: usize,
+    affinity: Affinity,
+) -> Option<usize> {
+    let last_line = line_metrics.len().checked_sub(1)?;
+    let line = line_metrics
+        .iter()
+        .position(|lm| offset >= lm.start_index && offset < lm.end_including_newline)
+        .or_else(|| {
+            if offset == line_metrics[last_line].end_including_newline {
+                Some(last_line)
+            } else {
+                None
+            }
+        })?;
+
+    match affinity {
+        Affinity::Upstream
+            if line > 0
+                && offset == line_metrics[line].start_index
+                && !line_metrics[line - 1].hard_break =>
+        {
+            Some(line - 1)
+        }
+        _ => Some(line),
+    }
 }
 
 pub type TextBoxes = Handle<sb::TextBoxes>;
@@ -141,6 +293,9 @@ impl Handle<sb::TextBoxes> {
         unsafe {
             let mut count = 0;
             let ptr = sb::C_TextBoxes_ptr_count(self.native(), &mut count);
+            if count == 0 {
+                return &[];
+            }
             std::slice::from_raw_parts(ptr as *const TextBox, count)
         }
     }
@@ -176,6 +331,9 @@ impl<'a> Borrows<'a, Handle<sb::LineMetricsVector>> {
         unsafe {
             let mut count = 0;
             let ptr = sb::C_LineMetricsVector_ptr_count(self.native(), &mut count);
+            if count == 0 {
+                return &[];
+            }
             std::slice::from_raw_parts(ptr as *const LineMetrics, count)
         }
     }
@@ -216,3 +374,140 @@ fn test_line_metrics() {
 
     static LOREM_IPSUM: &str = "Lorem ipsum dolor sit amet, consectetur adipiscing elit. Curabitur at leo at nulla tincidunt placerat. Proin eget purus augue. Quisque et est ullamcorper, pellentesque felis nec, pulvinar massa. Aliquam imperdiet, nulla ut dictum euismod, purus dui pulvinar risus, eu suscipit elit neque ac est. Nullam eleifend justo quis placerat ultricies. Vestibulum ut elementum velit. Praesent et dolor sit amet purus bibendum mattis. Aliquam erat volutpat.";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Paragraph;
+    use crate::icu;
+    use crate::textlayout::{
+        Affinity, FontCollection, ParagraphBuilder, ParagraphStyle, TextDirection, TextStyle,
+    };
+    use crate::FontMgr;
+
+    fn paragraph(text: &str, width: f32) -> Paragraph {
+        icu::init();
+
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
+        #[cfg(target_os = "macos")]
+        font_collection.disable_font_fallback();
+
+        let paragraph_style = ParagraphStyle::new();
+        let mut paragraph_builder = ParagraphBuilder::new(&paragraph_style, font_collection);
+        paragraph_builder.push_style(&TextStyle::new());
+        paragraph_builder.add_text(text);
+        let mut paragraph = paragraph_builder.build();
+        paragraph.layout(width);
+        paragraph
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn grapheme_navigation() {
+        // e + combining acute accent, a regional indicator flag, and x.
+        let text = "e\u{301}\u{1F1E9}\u{1F1EA}x";
+        let paragraph = paragraph(text, 1000.0);
+        assert_eq!(paragraph.text(), text);
+
+        assert_eq!(paragraph.next_grapheme_offset(0), 3);
+        assert_eq!(paragraph.next_grapheme_offset(3), 11);
+        assert_eq!(paragraph.next_grapheme_offset(11), 12);
+        assert_eq!(paragraph.next_grapheme_offset(12), 12);
+
+        assert_eq!(paragraph.previous_grapheme_offset(12), 11);
+        assert_eq!(paragraph.previous_grapheme_offset(11), 3);
+        assert_eq!(paragraph.previous_grapheme_offset(3), 0);
+        assert_eq!(paragraph.previous_grapheme_offset(0), 0);
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn hard_line_breaks() {
+        let text = "first line\nsecond line";
+        let mut paragraph = paragraph(text, 1000.0);
+        let second = text.find("second").unwrap();
+
+        assert_eq!(paragraph.line_number(), 2);
+        assert_eq!(
+            paragraph.get_line_number_at(0, Affinity::Downstream),
+            Some(0)
+        );
+        assert_eq!(
+            paragraph.get_line_number_at(second, Affinity::Upstream),
+            Some(1)
+        );
+        assert_eq!(
+            paragraph.get_line_number_at(second, Affinity::Downstream),
+            Some(1)
+        );
+        assert_eq!(
+            paragraph.get_line_number_at(text.len(), Affinity::Downstream),
+            Some(1)
+        );
+        assert_eq!(
+            paragraph.get_line_number_at(text.len() + 1, Affinity::Downstream),
+            None
+        );
+
+        assert_eq!(paragraph.get_line_range(0).unwrap().start, 0);
+        assert_eq!(paragraph.get_line_range(1), Some(second..text.len()));
+        assert_eq!(paragraph.get_line_range(2), None);
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn soft_line_breaks_respect_affinity() {
+        let text = "wrapping words wrapping words wrapping words";
+        let mut paragraph = paragraph(text, 100.0);
+        assert!(paragraph.line_number() > 1);
+
+        let start = paragraph.get_line_range(1).unwrap().start;
+        assert_eq!(
+            paragraph.get_line_number_at(start, Affinity::Downstream),
+            Some(1)
+        );
+        assert_eq!(
+            paragraph.get_line_number_at(start, Affinity::Upstream),
+            Some(0)
+        );
+
+        let upstream = paragraph.get_caret(start, Affinity::Upstream).unwrap();
+        let downstream = paragraph.get_caret(start, Affinity::Downstream).unwrap();
+        assert!(upstream.rect.bottom <= downstream.rect.top + 1.0);
+        assert!(upstream.rect.left > downstream.rect.left);
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    #[allow(clippy::float_cmp)]
+    fn carets_in_bidi_text() {
+        let text = "abc \u{5D0}\u{5D1}\u{5D2} def";
+        let mut paragraph = paragraph(text, 1000.0);
+        let caret = |paragraph: &mut Paragraph, offset| {
+            paragraph.get_caret(offset, Affinity::Downstream).unwrap()
+        };
+
+        // left to right
+        let a = caret(&mut paragraph, 0);
+        let b = caret(&mut paragraph, 1);
+        assert_eq!(a.direct, TextDirection::LTR);
+        assert!(a.rect.top < a.rect.bottom);
+        assert_eq!(a.rect.left, a.rect.right);
+        assert!(b.rect.left > a.rect.left);
+
+        // right to left: the carets move to the left while the offset increases.
+        let alef = caret(&mut paragraph, 4);
+        let bet = caret(&mut paragraph, 6);
+        let gimel = caret(&mut paragraph, 8);
+        assert_eq!(alef.direct, TextDirection::RTL);
+        assert_eq!(bet.direct, TextDirection::RTL);
+        assert!(bet.rect.left < alef.rect.left);
+        assert!(gimel.rect.left < bet.rect.left);
+        assert!(alef.rect.left > b.rect.left);
+
+        // the end of the text is behind the trailing edge of the last grapheme.
+        let end = caret(&mut paragraph, text.len());
+        assert_eq!(end.direct, TextDirection::LTR);
+        assert!(end.rect.left > alef.rect.left);
+    }
+}
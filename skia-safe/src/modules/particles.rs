@@ -0,0 +1,149 @@
+//! Particle effects.
+
+use crate::prelude::*;
+use crate::Canvas;
+use skia_bindings as sb;
+use skia_bindings::{SkParticleEffect, SkParticleEffectParams, SkRefCntBase};
+
+/// The definition of a particle effect that can be shared by multiple effect instances.
+pub type ParticleEffectParams = RCHandle<SkParticleEffectParams>;
+
+impl NativeBase<SkRefCntBase> for SkParticleEffectParams {}
+
+impl NativeRefCountedBase for SkParticleEffectParams {
+    type Base = SkRefCntBase;
+}
+
+impl RCHandle<SkParticleEffectParams> {
+    /// Loads an effect definition from its JSON representation.
+    ///
+    /// Returns `None` if `json` is not a JSON object. Fields that are missing or invalid keep
+    /// their default values.
+    pub fn from_json(json: impl AsRef<str>) -> Option<Self> {
+        let json = json.as_ref();
+        ParticleEffectParams::from_ptr(unsafe {
+            sb::C_SkParticleEffectParams_MakeFromJSON(json.as_ptr() as _, json.len())
+        })
+    }
+}
+
+/// An instance of a particle effect that spawns, updates and draws its particles.
+///
+/// Skia drives effects by absolute times in seconds. The effect remembers the time it was last
+/// started or updated at, so it can also be advanced by a time delta with
+/// [`ParticleEffect::update_by()`].
+pub struct ParticleEffect {
+    effect: RCHandle<SkParticleEffect>,
+    time: f64,
+}
+
+impl NativeBase<SkRefCntBase> for SkParticleEffect {}
+
+impl NativeRefCountedBase for SkParticleEffect {
+    type Base = SkRefCntBase;
+}
+
+impl ParticleEffect {
+    /// Creates an effect from `params`, `seed` initializes the random number generator of the
+    /// effect.
+    pub fn new(params: &ParticleEffectParams, seed: impl Into<Option<u32>>) -> Self {
+        let effect = RCHandle::from_ptr(unsafe {
+            sb::C_SkParticleEffect_new(params.clone().into_ptr(), seed.into().unwrap_or_default())
+        })
+        .unwrap();
+        ParticleEffect { effect, time: 0.0 }
+    }
+
+    /// Starts the effect at the time `now`, in seconds.
+    ///
+    /// If `looping` is set, the effect restarts after its duration has passed.
+    pub fn start(&mut self, now: f64, looping: bool) -> &mut Self {
+        unsafe { sb::C_SkParticleEffect_start(self.effect.native_mut(), now, looping) }
+        self.time = now;
+        self
+    }
+
+    /// Spawns, updates and removes particles up to the time `now`, in seconds.
+    ///
+    /// `now` is measured on the same clock that was used to start the effect.
+    pub fn update(&mut self, now: f64) -> &mut Self {
+        unsafe { sb::C_SkParticleEffect_update(self.effect.native_mut(), now) }
+        self.time = now;
+        self
+    }
+
+    /// Advances the effect by `delta` seconds from the time it was last started or updated at.
+    pub fn update_by(&mut self, delta: f64) -> &mut Self {
+        self.update(self.time + delta)
+    }
+
+    /// The time the effect was last started or updated at, in seconds.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn draw(&mut self, canvas: &mut Canvas) {
+        unsafe { sb::C_SkParticleEffect_draw(self.effect.native_mut(), canvas.native_mut()) }
+    }
+
+    /// Returns `true` if the effect was started and did not end yet.
+    pub fn is_alive(&self) -> bool {
+        unsafe { sb::C_SkParticleEffect_isAlive(self.effect.native()) }
+    }
+
+    /// The number of particles that are currently alive.
+    pub fn count(&self) -> usize {
+        unsafe { sb::C_SkParticleEffect_getCount(self.effect.native()) }
+            .try_into()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParticleEffect, ParticleEffectParams};
+    use crate::{Color, Surface};
+
+    const EFFECT: &str = r#"{
+        "MaxCount": 32,
+        "Drawable": { "Type": "SkCircleDrawable", "Radius": 2 }
+    }"#;
+
+    #[test]
+    fn invalid_json_is_rejected() {
+        assert!(ParticleEffectParams::from_json("[]").is_none());
+        assert!(ParticleEffectParams::from_json("{ not json").is_none());
+    }
+
+    #[test]
+    fn start_update_and_draw() {
+        let params = ParticleEffectParams::from_json(EFFECT).unwrap();
+        let mut effect = ParticleEffect::new(&params, 42);
+        effect.start(0.0, true);
+        assert!(effect.is_alive());
+
+        let mut surface = Surface::new_raster_n32_premul((64, 64)).unwrap();
+        for frame in 1..=10 {
+            surface.canvas().clear(Color::TRANSPARENT);
+            effect.update(f64::from(frame) / 60.0);
+            effect.draw(surface.canvas());
+        }
+        assert!(effect.is_alive());
+        assert!(effect.count() <= 32);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn update_by_advances_from_the_last_time() {
+        let params = ParticleEffectParams::from_json(EFFECT).unwrap();
+        let mut effect = ParticleEffect::new(&params, None);
+        effect.start(10.0, true);
+        assert_eq!(effect.time(), 10.0);
+
+        effect.update_by(0.5).update_by(0.25);
+        assert_eq!(effect.time(), 10.75);
+        effect.update(20.0).update_by(1.0);
+        assert_eq!(effect.time(), 21.0);
+        assert!(effect.is_alive());
+    }
+}
@@ -1,14 +1,17 @@
 use crate::prelude::*;
-use crate::{scalar, Font, FontMgr, FourByteTag, Point, TextBlob};
+use crate::{scalar, Font, FontMgr, FontStyle, FourByteTag, Point, TextBlob};
 pub use run_handler::RunHandler;
+pub use run_iterators::{BiDiRuns, FontRuns, LanguageRuns, ScriptRuns};
+pub use shaped_text::{ShapedLine, ShapedRun, ShapedText, ShapedTextCollector};
 use skia_bindings as sb;
 use skia_bindings::{
     SkShaper, SkShaper_BiDiRunIterator, SkShaper_FontRunIterator, SkShaper_LanguageRunIterator,
     SkShaper_RunIterator, SkShaper_ScriptRunIterator, SkTextBlobBuilderRunHandler,
 };
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::os::raw;
+use std::ptr;
 
 pub type Shaper = RefHandle<SkShaper>;
 unsafe impl Send for Shaper {}
@@ -53,12 +56,32 @@ impl RefHandle<SkShaper> {
     }
 }
 
+/// Segments the text into runs that are shaped separately.
+///
+/// Implement this trait together with [`FontRuns`], [`BiDiRuns`], [`ScriptRuns`] or
+/// [`LanguageRuns`] to provide a custom segmentation to [`Shaper::shape_with_iterators()`].
 pub trait RunIterator {
+    /// Advances to the next run.
     fn consume(&mut self);
+    /// The UTF-8 byte offset that ends the current run.
     fn end_of_current_run(&self) -> usize;
     fn at_end(&self) -> bool;
 }
 
+impl<T: RunIterator> RunIterator for Borrows<'_, T> {
+    fn consume(&mut self) {
+        (**self).consume()
+    }
+
+    fn end_of_current_run(&self) -> usize {
+        (**self).end_of_current_run()
+    }
+
+    fn at_end(&self) -> bool {
+        (**self).at_end()
+    }
+}
+
 impl<T> RunIterator for RefHandle<T>
 where
     T: NativeDrop,
@@ -114,8 +137,48 @@ impl RefHandle<SkShaper> {
         .borrows(utf8)
     }
 
-    // TODO: m79: wrap MakeFontMgrRunIterator with requestName (borrowed), requestStyle and
-    //       a LanguageRunIterator.
+    /// Creates a font run iterator that looks up fallback fonts for the family `request_name`
+    /// and `request_style`, and for the language of the current run of `language`.
+    ///
+    /// The font run iterator reads the current language from `language` while it is advanced, so
+    /// both must be passed together to [`Shaper::shape_with_iterators()`], see
+    /// [`FontMgrRunIterator::iterators()`].
+    ///
+    /// Returns `None` if `request_name` contains a NUL character.
+    pub fn new_font_mgr_run_iterator_with_language<'a>(
+        utf8: &'a str,
+        font: &Font,
+        fallback: impl Into<Option<FontMgr>>,
+        request_name: Option<&str>,
+        request_style: FontStyle,
+        language: LanguageRunIterator,
+    ) -> Option<FontMgrRunIterator<'a>> {
+        let bytes = utf8.as_bytes();
+        let request_name = match request_name {
+            Some(name) => Some(CString::new(name).ok()?),
+            None => None,
+        };
+        let font_runs = FontRunIterator::from_ptr(unsafe {
+            sb::C_SkShaper_MakeFontMgrRunIterator2(
+                bytes.as_ptr() as _,
+                bytes.len(),
+                font.native(),
+                fallback.into().into_ptr_or_null(),
+                request_name
+                    .as_ref()
+                    .map(|name| name.as_ptr())
+                    .unwrap_or(ptr::null()),
+                request_style.native(),
+                language.native(),
+            )
+        })?;
+        Some(FontMgrRunIterator {
+            font_runs,
+            language_runs: language,
+            _request_name: request_name,
+            text: PhantomData,
+        })
+    }
 
     pub fn new_trivial_font_run_iterator(font: &Font, utf8_bytes: usize) -> FontRunIterator {
         FontRunIterator::from_ptr(unsafe {
@@ -125,6 +188,24 @@ impl RefHandle<SkShaper> {
     }
 }
 
+/// A font run iterator that selects fallback fonts for the language of the current run.
+pub struct FontMgrRunIterator<'a> {
+    // the font run iterator refers to the language run iterator and to the request name, so it
+    // must be dropped first.
+    font_runs: FontRunIterator,
+    language_runs: LanguageRunIterator,
+    _request_name: Option<CString>,
+    text: PhantomData<&'a str>,
+}
+
+impl FontMgrRunIterator<'_> {
+    /// Returns the font and the language run iterator, which must be passed together to
+    /// [`Shaper::shape_with_iterators()`].
+    pub fn iterators(&mut self) -> (&mut FontRunIterator, &mut LanguageRunIterator) {
+        (&mut self.font_runs, &mut self.language_runs)
+    }
+}
+
 pub type BiDiRunIterator = RefHandle<SkShaper_BiDiRunIterator>;
 
 impl NativeBase<SkShaper_RunIterator> for SkShaper_BiDiRunIterator {}
@@ -368,18 +449,27 @@ impl RefHandle<SkShaper> {
         }
     }
 
+    /// Shapes `utf8` with the segmentation the run iterators provide.
+    ///
+    /// The iterators may be the ones Skia creates, or types that implement the run iterator
+    /// traits in Rust.
     #[allow(clippy::too_many_arguments)]
     pub fn shape_with_iterators(
         &self,
         utf8: &str,
-        font_run_iterator: &mut FontRunIterator,
-        bidi_run_iterator: &mut BiDiRunIterator,
-        script_run_iterator: &mut ScriptRunIterator,
-        language_run_iterator: &mut LanguageRunIterator,
+        font_run_iterator: &mut dyn FontRuns,
+        bidi_run_iterator: &mut dyn BiDiRuns,
+        script_run_iterator: &mut dyn ScriptRuns,
+        language_run_iterator: &mut dyn LanguageRuns,
         width: scalar,
         run_handler: &mut dyn RunHandler,
     ) {
         let bytes = utf8.as_bytes();
+        let mut font_run_iterator = run_iterators::new_font_run_iterator(font_run_iterator);
+        let mut bidi_run_iterator = run_iterators::new_bidi_run_iterator(bidi_run_iterator);
+        let mut script_run_iterator = run_iterators::new_script_run_iterator(script_run_iterator);
+        let mut language_run_iterator =
+            run_iterators::new_language_run_iterator(language_run_iterator);
         let param = rust_run_handler::new_param(run_handler);
         let mut run_handler = rust_run_handler::from_param(&param);
         unsafe {
@@ -398,6 +488,185 @@ impl RefHandle<SkShaper> {
     }
 }
 
+mod run_iterators {
+    use super::{BiDiRunIterator, FontRunIterator, LanguageRunIterator, ScriptRunIterator};
+    use crate::prelude::*;
+    use crate::shaper::RunIterator;
+    use crate::{Font, FourByteTag};
+    use skia_bindings as sb;
+    use skia_bindings::{
+        RustBiDiRunIterator_Param, RustFontRunIterator_Param, RustLanguageRunIterator_Param,
+        RustRunIteratorParam, RustScriptRunIterator_Param, SkFont, SkFourByteTag, TraitObject,
+    };
+    use std::ffi::CStr;
+    use std::os::raw;
+
+    /// A [`RunIterator`] that segments the text into runs of the same font.
+    ///
+    /// Implement it to do font fallback in Rust.
+    pub trait FontRuns: RunIterator {
+        fn current_font(&self) -> &Font;
+    }
+
+    /// A [`RunIterator`] that segments the text into runs of the same bidi embedding level.
+    pub trait BiDiRuns: RunIterator {
+        fn current_level(&self) -> u8;
+    }
+
+    /// A [`RunIterator`] that segments the text into runs of the same script.
+    pub trait ScriptRuns: RunIterator {
+        fn current_script(&self) -> FourByteTag;
+    }
+
+    /// A [`RunIterator`] that segments the text into runs of the same language.
+    pub trait LanguageRuns: RunIterator {
+        fn current_language(&self) -> &CStr;
+    }
+
+    impl FontRuns for FontRunIterator {
+        fn current_font(&self) -> &Font {
+            FontRunIterator::current_font(self)
+        }
+    }
+
+    impl BiDiRuns for BiDiRunIterator {
+        fn current_level(&self) -> u8 {
+            BiDiRunIterator::current_level(self)
+        }
+    }
+
+    impl ScriptRuns for ScriptRunIterator {
+        fn current_script(&self) -> FourByteTag {
+            ScriptRunIterator::current_script(self)
+        }
+    }
+
+    impl LanguageRuns for LanguageRunIterator {
+        fn current_language(&self) -> &CStr {
+            LanguageRunIterator::current_language(self)
+        }
+    }
+
+    impl<T: FontRuns> FontRuns for Borrows<'_, T> {
+        fn current_font(&self) -> &Font {
+            (**self).current_font()
+        }
+    }
+
+    impl<T: BiDiRuns> BiDiRuns for Borrows<'_, T> {
+        fn current_level(&self) -> u8 {
+            (**self).current_level()
+        }
+    }
+
+    impl<T: ScriptRuns> ScriptRuns for Borrows<'_, T> {
+        fn current_script(&self) -> FourByteTag {
+            (**self).current_script()
+        }
+    }
+
+    impl<T: LanguageRuns> LanguageRuns for Borrows<'_, T> {
+        fn current_language(&self) -> &CStr {
+            (**self).current_language()
+        }
+    }
+
+    // The native iterators returned refer to the Rust iterators, so they must be dropped before
+    // the Rust iterators go out of scope.
+
+    pub fn new_font_run_iterator(iterator: &mut dyn FontRuns) -> FontRunIterator {
+        let param = RustFontRunIterator_Param {
+            iterator: run_iterator_param::<dyn FontRuns>(iterator),
+            currentFont: Some(current_font),
+        };
+        FontRunIterator::from_ptr(unsafe { sb::C_RustFontRunIterator_new(&param) }).unwrap()
+    }
+
+    pub fn new_bidi_run_iterator(iterator: &mut dyn BiDiRuns) -> BiDiRunIterator {
+        let param = RustBiDiRunIterator_Param {
+            iterator: run_iterator_param::<dyn BiDiRuns>(iterator),
+            currentLevel: Some(current_level),
+        };
+        BiDiRunIterator::from_ptr(unsafe { sb::C_RustBiDiRunIterator_new(&param) }).unwrap()
+    }
+
+    pub fn new_script_run_iterator(iterator: &mut dyn ScriptRuns) -> ScriptRunIterator {
+        let param = RustScriptRunIterator_Param {
+            iterator: run_iterator_param::<dyn ScriptRuns>(iterator),
+            currentScript: Some(current_script),
+        };
+        ScriptRunIterator::from_ptr(unsafe { sb::C_RustScriptRunIterator_new(&param) }).unwrap()
+    }
+
+    pub fn new_language_run_iterator(iterator: &mut dyn LanguageRuns) -> LanguageRunIterator {
+        let param = RustLanguageRunIterator_Param {
+            iterator: run_iterator_param::<dyn LanguageRuns>(iterator),
+            currentLanguage: Some(current_language),
+        };
+        LanguageRunIterator::from_ptr(unsafe { sb::C_RustLanguageRunIterator_new(&param) }).unwrap()
+    }
+
+    fn run_iterator_param<T: RunIteratorObject + ?Sized>(iterator: &mut T) -> RustRunIteratorParam {
+        RustRunIteratorParam {
+            trait_: unsafe { to_trait_object(iterator) },
+            consume: Some(consume::<T>),
+            endOfCurrentRun: Some(end_of_current_run::<T>),
+            atEnd: Some(at_end::<T>),
+        }
+    }
+
+    extern "C" fn consume<T: RunIteratorObject + ?Sized>(to: TraitObject) {
+        unsafe { from_trait_object::<T>(to) }.consume()
+    }
+
+    extern "C" fn end_of_current_run<T: RunIteratorObject + ?Sized>(to: TraitObject) -> usize {
+        unsafe { from_trait_object::<T>(to) }.end_of_current_run()
+    }
+
+    extern "C" fn at_end<T: RunIteratorObject + ?Sized>(to: TraitObject) -> bool {
+        unsafe { from_trait_object::<T>(to) }.at_end()
+    }
+
+    extern "C" fn current_font(to: TraitObject) -> *const SkFont {
+        unsafe { from_trait_object::<dyn FontRuns>(to) }
+            .current_font()
+            .native()
+    }
+
+    extern "C" fn current_level(to: TraitObject) -> u8 {
+        unsafe { from_trait_object::<dyn BiDiRuns>(to) }.current_level()
+    }
+
+    extern "C" fn current_script(to: TraitObject) -> SkFourByteTag {
+        unsafe { from_trait_object::<dyn ScriptRuns>(to) }
+            .current_script()
+            .into_native()
+    }
+
+    extern "C" fn current_language(to: TraitObject) -> *const raw::c_char {
+        unsafe { from_trait_object::<dyn LanguageRuns>(to) }
+            .current_language()
+            .as_ptr()
+    }
+
+    // Implemented by the trait object types of the run iterators only, so that references to
+    // them have the layout of a TraitObject. The generic functions are only needed to be able to
+    // call the RunIterator functions through the vtable of the derived trait.
+    trait RunIteratorObject: RunIterator {}
+    impl<'a> RunIteratorObject for dyn FontRuns + 'a {}
+    impl<'a> RunIteratorObject for dyn BiDiRuns + 'a {}
+    impl<'a> RunIteratorObject for dyn ScriptRuns + 'a {}
+    impl<'a> RunIteratorObject for dyn LanguageRuns + 'a {}
+
+    unsafe fn to_trait_object<T: RunIteratorObject + ?Sized>(iterator: &mut T) -> TraitObject {
+        std::mem::transmute_copy(&iterator)
+    }
+
+    unsafe fn from_trait_object<'a, T: RunIteratorObject + ?Sized>(to: TraitObject) -> &'a mut T {
+        std::mem::transmute_copy(&to)
+    }
+}
+
 mod rust_run_handler {
     use crate::prelude::*;
     use crate::shaper::run_handler::RunInfo;
@@ -526,6 +795,147 @@ impl RefHandle<SkShaper> {
     }
 }
 
+mod shaped_text {
+    use super::run_handler::{Buffer, RunHandler, RunInfo};
+    use crate::{scalar, Font, GlyphId, Point, Vector};
+    use std::mem;
+    use std::ops::Range;
+
+    /// The result of shaping a text, collected into owned types.
+    #[derive(Clone, Default)]
+    pub struct ShapedText {
+        pub lines: Vec<ShapedLine>,
+        /// The position below the last line, where a following text would start.
+        pub end_point: Point,
+    }
+
+    #[derive(Clone, Default)]
+    pub struct ShapedLine {
+        pub runs: Vec<ShapedRun>,
+    }
+
+    #[derive(Clone)]
+    pub struct ShapedRun {
+        pub font: Font,
+        pub bidi_level: u8,
+        pub utf8_range: Range<usize>,
+        pub glyphs: Vec<GlyphId>,
+        /// The absolute positions of the glyphs, the line's baseline included.
+        pub positions: Vec<Point>,
+        /// The UTF-8 offsets into the shaped text each glyph belongs to.
+        pub clusters: Vec<u32>,
+        pub advance: Vector,
+    }
+
+    impl ShapedRun {
+        pub fn is_left_to_right(&self) -> bool {
+            self.bidi_level & 1 == 0
+        }
+    }
+
+    /// A [`RunHandler`] that lays out the lines like the `TextBlobBuilderRunHandler` does and
+    /// collects the runs into a [`ShapedText`].
+    #[derive(Default)]
+    pub struct ShapedTextCollector {
+        text: ShapedText,
+        line: ShapedLine,
+        offset: Point,
+        current_position: Point,
+        max_run_ascent: scalar,
+        max_run_descent: scalar,
+        max_run_leading: scalar,
+        glyphs: Vec<GlyphId>,
+        positions: Vec<Point>,
+        clusters: Vec<u32>,
+    }
+
+    impl ShapedTextCollector {
+        pub fn new(offset: impl Into<Point>) -> Self {
+            let offset = offset.into();
+            ShapedTextCollector {
+                offset,
+                current_position: offset,
+                ..Default::default()
+            }
+        }
+
+        pub fn finish(self) -> ShapedText {
+            ShapedText {
+                end_point: self.offset,
+                ..self.text
+            }
+        }
+    }
+
+    impl RunHandler for ShapedTextCollector {
+        fn begin_line(&mut self) {
+            self.current_position = self.offset;
+            self.max_run_ascent = 0.0;
+            self.max_run_descent = 0.0;
+            self.max_run_leading = 0.0;
+        }
+
+        fn run_info(&mut self, info: &RunInfo) {
+            let (_, metrics) = info.font.metrics();
+            self.max_run_ascent = self.max_run_ascent.min(metrics.ascent);
+            self.max_run_descent = self.max_run_descent.max(metrics.descent);
+            self.max_run_leading = self.max_run_leading.max(metrics.leading);
+        }
+
+        fn commit_run_info(&mut self) {
+            self.current_position.y -= self.max_run_ascent;
+        }
+
+        fn run_buffer<'a>(&'a mut self, info: &RunInfo) -> Buffer<'a> {
+            let count = info.glyph_count;
+            self.glyphs = vec![0; count];
+            self.positions = vec![Point::default(); count];
+            self.clusters = vec![0; count];
+            Buffer {
+                glyphs: &mut self.glyphs,
+                positions: &mut self.positions,
+                offsets: None,
+                clusters: Some(&mut self.clusters),
+                point: self.current_position,
+            }
+        }
+
+        fn commit_run_buffer(&mut self, info: &RunInfo) {
+            self.line.runs.push(ShapedRun {
+                font: info.font.clone(),
+                bidi_level: info.bidi_level,
+                utf8_range: info.utf8_range.clone(),
+                glyphs: mem::take(&mut self.glyphs),
+                positions: mem::take(&mut self.positions),
+                clusters: mem::take(&mut self.clusters),
+                advance: info.advance,
+            });
+            self.current_position += info.advance;
+        }
+
+        fn commit_line(&mut self) {
+            self.text.lines.push(mem::take(&mut self.line));
+            self.offset.y += self.max_run_descent + self.max_run_leading - self.max_run_ascent;
+        }
+    }
+}
+
+impl RefHandle<SkShaper> {
+    /// Shapes `text` and returns the lines, runs and glyphs in owned types.
+    pub fn shape_text(
+        &self,
+        text: &str,
+        font: &Font,
+        left_to_right: bool,
+        width: scalar,
+        offset: impl Into<Point>,
+    ) -> ShapedText {
+        let mut collector = ShapedTextCollector::new(offset);
+        self.shape(text, font, left_to_right, width, &mut collector);
+        collector.finish()
+    }
+}
+
 pub mod icu {
     /// On Windows, this function writes the file `icudtl.dat` into the current
     /// executable's directory making sure that it's available when text shaping is used in Skia.
@@ -542,8 +952,14 @@ pub mod icu {
 #[cfg(test)]
 mod tests {
     use crate::shaper::run_handler::{Buffer, RunInfo};
-    use crate::shaper::RunHandler;
-    use crate::{Font, GlyphId, Point, Shaper};
+    use crate::shaper::run_iterators;
+    use crate::shaper::{BiDiRuns, FontRuns, LanguageRuns, RunHandler, RunIterator, ScriptRuns};
+    use crate::{
+        scalar, Font, FontMgr, FontStyle, FourByteTag, GlyphId, Point, Shaper, Typeface, Unichar,
+    };
+    use std::cell::Cell;
+    use std::ffi::{CStr, CString};
+    use std::ops::Range;
 
     #[derive(Default, Debug)]
     pub struct DebugRunHandler {
@@ -582,6 +998,360 @@ mod tests {
         }
     }
 
+    /// Splits the text into runs at the given UTF-8 offsets.
+    struct Runs<T> {
+        ends: Vec<(usize, T)>,
+        current: usize,
+    }
+
+    impl<T> Runs<T> {
+        fn new(ends: Vec<(usize, T)>) -> Self {
+            Runs { ends, current: 0 }
+        }
+
+        fn value(&self) -> &T {
+            &self.ends[self.current.min(self.ends.len() - 1)].1
+        }
+    }
+
+    impl<T> RunIterator for Runs<T> {
+        fn consume(&mut self) {
+            self.current += 1;
+        }
+
+        fn end_of_current_run(&self) -> usize {
+            self.ends[self.current].0
+        }
+
+        fn at_end(&self) -> bool {
+            self.current == self.ends.len()
+        }
+    }
+
+    impl FontRuns for Runs<Font> {
+        fn current_font(&self) -> &Font {
+            self.value()
+        }
+    }
+
+    impl BiDiRuns for Runs<u8> {
+        fn current_level(&self) -> u8 {
+            *self.value()
+        }
+    }
+
+    impl ScriptRuns for Runs<FourByteTag> {
+        fn current_script(&self) -> FourByteTag {
+            *self.value()
+        }
+    }
+
+    impl LanguageRuns for Runs<CString> {
+        fn current_language(&self) -> &CStr {
+            self.value()
+        }
+    }
+
+    #[derive(Default)]
+    struct RunSizes(Vec<(scalar, Range<usize>)>, Vec<GlyphId>, Vec<Point>);
+
+    impl RunHandler for RunSizes {
+        fn begin_line(&mut self) {}
+        fn run_info(&mut self, _info: &RunInfo) {}
+        fn commit_run_info(&mut self) {}
+
+        fn run_buffer<'a>(&'a mut self, info: &RunInfo) -> Buffer {
+            self.1.resize(info.glyph_count, 0);
+            self.2.resize(info.glyph_count, Point::default());
+            Buffer::new(&mut self.1, &mut self.2, None)
+        }
+
+        fn commit_run_buffer(&mut self, info: &RunInfo) {
+            self.0.push((info.font.size(), info.utf8_range.clone()))
+        }
+
+        fn commit_line(&mut self) {}
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn shape_with_rust_run_iterators() {
+        skia_bindings::icu::init();
+
+        let text = "abcdef";
+        let small = Font::new(Typeface::default(), 10.0);
+        let large = Font::new(Typeface::default(), 20.0);
+        let mut fonts = Runs::new(vec![(2, small), (6, large)]);
+        let mut levels = Runs::new(vec![(6, 0)]);
+        let mut scripts = Runs::new(vec![(6, FourByteTag::from_chars(b'L', b'a', b't', b'n'))]);
+        let mut languages = Runs::new(vec![(6, CString::new("en").unwrap())]);
+        let mut run_sizes = RunSizes::default();
+
+        Shaper::new(None).shape_with_iterators(
+            text,
+            &mut fonts,
+            &mut levels,
+            &mut scripts,
+            &mut languages,
+            10000.0,
+            &mut run_sizes,
+        );
+
+        assert_eq!(run_sizes.0, vec![(10.0, 0..2), (20.0, 2..6)]);
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn shape_with_native_run_iterators() {
+        skia_bindings::icu::init();
+
+        let text = "abcdef";
+        let font = Font::default();
+        let mut fonts = Shaper::new_trivial_font_run_iterator(&font, text.len());
+        let mut levels = Shaper::new_bidi_run_iterator(text, 0).unwrap();
+        let mut scripts = Shaper::new_hb_icu_script_run_iterator(text);
+        let mut languages = Shaper::new_trivial_language_run_iterator("en");
+        let mut run_sizes = RunSizes::default();
+
+        Shaper::new(None).shape_with_iterators(
+            text,
+            &mut fonts,
+            &mut levels,
+            &mut scripts,
+            &mut languages,
+            10000.0,
+            &mut run_sizes,
+        );
+
+        assert_eq!(run_sizes.0, vec![(font.size(), 0..6)]);
+    }
+
+    /// Returns the family names of the fonts the iterator selects for each run.
+    fn font_families(font_runs: &mut impl FontRuns) -> Vec<String> {
+        let mut families = Vec::new();
+        while !font_runs.at_end() {
+            font_runs.consume();
+            families.push(font_runs.current_font().typeface_or_default().family_name());
+        }
+        families
+    }
+
+    /// A language run that counts how often its language is read.
+    struct CountedLanguage {
+        runs: Runs<CString>,
+        reads: Cell<usize>,
+    }
+
+    impl CountedLanguage {
+        fn new(language: &str, utf8_bytes: usize) -> Self {
+            CountedLanguage {
+                runs: Runs::new(vec![(utf8_bytes, CString::new(language).unwrap())]),
+                reads: Cell::new(0),
+            }
+        }
+    }
+
+    impl RunIterator for CountedLanguage {
+        fn consume(&mut self) {
+            self.runs.consume()
+        }
+
+        fn end_of_current_run(&self) -> usize {
+            self.runs.end_of_current_run()
+        }
+
+        fn at_end(&self) -> bool {
+            self.runs.at_end()
+        }
+    }
+
+    impl LanguageRuns for CountedLanguage {
+        fn current_language(&self) -> &CStr {
+            self.reads.set(self.reads.get() + 1);
+            self.runs.current_language()
+        }
+    }
+
+    // U+76F4 is unified in Unicode, but its glyph differs between Japanese, Chinese and Korean
+    // fonts.
+    const UNIFIED_HAN: &str = "直";
+    const CJK_LANGUAGES: [&str; 3] = ["ja", "zh-Hans", "ko"];
+
+    /// A font that does not contain `character`, so that a fallback font is looked up for it.
+    fn font_without(character: Unichar) -> Font {
+        let font_mgr = FontMgr::default();
+        let typeface = (0..font_mgr.count_families())
+            .filter_map(|i| {
+                font_mgr.match_family_style(font_mgr.family_name(i), FontStyle::normal())
+            })
+            .find(|typeface| typeface.unichar_to_glyph(character) == 0)
+            .expect("all installed fonts contain the character");
+        Font::from_typeface(typeface, None)
+    }
+
+    /// The family the font manager falls back to for `character` in `language`.
+    fn fallback_family(font: &Font, character: Unichar, language: &str) -> String {
+        FontMgr::default()
+            .match_family_style_character("sans-serif", FontStyle::normal(), &[language], character)
+            .unwrap_or_else(|| font.typeface_or_default())
+            .family_name()
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn font_mgr_run_iterator_looks_up_fallbacks_for_the_current_language() {
+        skia_bindings::icu::init();
+
+        let character = UNIFIED_HAN.chars().next().unwrap() as Unichar;
+        let font = font_without(character);
+        for &language in &CJK_LANGUAGES {
+            let mut counted = CountedLanguage::new(language, UNIFIED_HAN.len());
+            let native = run_iterators::new_language_run_iterator(&mut counted);
+            let mut font_mgr_runs = Shaper::new_font_mgr_run_iterator_with_language(
+                UNIFIED_HAN,
+                &font,
+                FontMgr::default(),
+                Some("sans-serif"),
+                FontStyle::normal(),
+                native,
+            )
+            .unwrap();
+            let families = font_families(font_mgr_runs.iterators().0);
+            drop(font_mgr_runs);
+
+            assert!(counted.reads.get() > 0);
+            assert_eq!(families, vec![fallback_family(&font, character, language)]);
+        }
+    }
+
+    // Needs Japanese, Chinese and Korean fonts the font manager tells apart, run it with
+    // `cargo test -- --ignored` on a system that has them installed.
+    #[test]
+    #[ignore]
+    #[serial_test_derive::serial]
+    fn cjk_fallback_depends_on_the_language() {
+        skia_bindings::icu::init();
+
+        let character = UNIFIED_HAN.chars().next().unwrap() as Unichar;
+        let font = font_without(character);
+        let families: Vec<Vec<String>> = CJK_LANGUAGES
+            .iter()
+            .map(|&language| {
+                let mut font_mgr_runs = Shaper::new_font_mgr_run_iterator_with_language(
+                    UNIFIED_HAN,
+                    &font,
+                    FontMgr::default(),
+                    Some("sans-serif"),
+                    FontStyle::normal(),
+                    Shaper::new_trivial_language_run_iterator(language),
+                )
+                .unwrap();
+                let (font_runs, language_runs) = font_mgr_runs.iterators();
+                assert_eq!(language_runs.current_language().to_str().unwrap(), language);
+                font_families(font_runs)
+            })
+            .collect();
+
+        assert!(families.iter().any(|family| *family != families[0]));
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn font_mgr_run_iterator_rejects_nul_in_request_name() {
+        assert!(Shaper::new_font_mgr_run_iterator_with_language(
+            "text",
+            &Font::default(),
+            None,
+            Some("sans\0serif"),
+            FontStyle::normal(),
+            Shaper::new_trivial_language_run_iterator("en"),
+        )
+        .is_none());
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn shape_with_font_mgr_run_iterator() {
+        skia_bindings::icu::init();
+
+        let text = "日本語のテキスト";
+        let mut font_mgr_runs = Shaper::new_font_mgr_run_iterator_with_language(
+            text,
+            &Font::default(),
+            None,
+            None,
+            FontStyle::default(),
+            Shaper::new_trivial_language_run_iterator("ja"),
+        )
+        .unwrap();
+        let (font_runs, language_runs) = font_mgr_runs.iterators();
+        let mut levels = Shaper::new_trivial_bidi_run_iterator(0, text.len());
+        let mut scripts = Shaper::new_hb_icu_script_run_iterator(text);
+        let mut run_sizes = RunSizes::default();
+
+        Shaper::new(None).shape_with_iterators(
+            text,
+            font_runs,
+            &mut levels,
+            &mut scripts,
+            language_runs,
+            10000.0,
+            &mut run_sizes,
+        );
+
+        let last = run_sizes.0.last().unwrap();
+        assert_eq!(last.1.end, text.len());
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    #[allow(clippy::float_cmp)]
+    fn shape_text_collects_lines_and_runs() {
+        skia_bindings::icu::init();
+
+        let text = "Hello World, this text wraps";
+        let font = Font::default();
+        let shaped = Shaper::new(None).shape_text(text, &font, true, 50.0, (10.0, 20.0));
+
+        assert!(shaped.lines.len() > 1);
+        let mut baseline = 20.0;
+        for line in &shaped.lines {
+            let first_run = &line.runs[0];
+            assert!(first_run.positions[0].y > baseline);
+            baseline = first_run.positions[0].y;
+            for run in &line.runs {
+                assert!(run.is_left_to_right());
+                assert_eq!(run.font.size(), font.size());
+                assert_eq!(run.glyphs.len(), run.positions.len());
+                assert_eq!(run.glyphs.len(), run.clusters.len());
+                assert!(run.advance.x > 0.0);
+                for &cluster in &run.clusters {
+                    assert!(run.utf8_range.contains(&(cluster as usize)));
+                }
+            }
+        }
+        assert_eq!(shaped.lines[0].runs[0].positions[0].x, 10.0);
+        assert!(shaped.end_point.y > baseline);
+
+        let last_run = shaped.lines.last().unwrap().runs.last().unwrap();
+        assert_eq!(last_run.utf8_range.end, text.len());
+    }
+
+    #[test]
+    #[serial_test_derive::serial]
+    fn shape_rtl_text_collects_rtl_runs() {
+        skia_bindings::icu::init();
+
+        let text = "العربية";
+        let shaped =
+            Shaper::new(None).shape_text(text, &Font::default(), false, 10000.0, Point::default());
+
+        assert_eq!(shaped.lines.len(), 1);
+        let run = &shaped.lines[0].runs[0];
+        assert!(!run.is_left_to_right());
+        assert_eq!(run.utf8_range, 0..text.len());
+    }
+
     #[test]
     #[serial_test_derive::serial]
     fn test_rtl_text_shaping() {
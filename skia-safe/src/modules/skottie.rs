@@ -0,0 +1,13 @@
+//! Lottie animation playback.
+
+mod animation;
+pub use animation::*;
+
+mod logger;
+pub use logger::*;
+
+mod property;
+pub use property::*;
+
+mod resource_provider;
+pub use resource_provider::*;
@@ -0,0 +1,226 @@
+use super::{
+    new_logger_param, new_property_observer_param, new_resource_provider_param, Logger,
+    PropertyObserver, ResourceProvider,
+};
+use crate::interop::AsStr;
+use crate::prelude::*;
+use crate::{scalar, Canvas, FontMgr, Rect, Size};
+use skia_bindings as sb;
+use skia_bindings::{skottie_Animation, skottie_Animation_Builder};
+
+/// A Lottie animation.
+// m79 does not expose the in and out points of an animation, only its `duration()`, which is
+// (out point - in point) / fps.
+pub type Animation = RCHandle<skottie_Animation>;
+
+impl NativeRefCounted for skottie_Animation {
+    fn _ref(&self) {
+        unsafe { sb::C_skottie_Animation_ref(self) }
+    }
+
+    fn _unref(&self) {
+        unsafe { sb::C_skottie_Animation_unref(self) }
+    }
+
+    fn unique(&self) -> bool {
+        unsafe { sb::C_skottie_Animation_unique(self) }
+    }
+}
+
+impl RCHandle<skottie_Animation> {
+    /// Builds an animation from the Lottie JSON in `bytes` with the default [`Builder`].
+    pub fn from_bytes(bytes: &[u8]) -> Option<Animation> {
+        Builder::new().make_from_bytes(bytes)
+    }
+
+    /// Builds an animation from the Lottie JSON in `str` with the default [`Builder`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(str: impl AsRef<str>) -> Option<Animation> {
+        Self::from_bytes(str.as_ref().as_bytes())
+    }
+
+    /// The Lottie format version the animation was exported with.
+    pub fn version(&self) -> &str {
+        unsafe { &*sb::C_skottie_Animation_version(self.native()) }.as_str()
+    }
+
+    /// The duration in seconds.
+    pub fn duration(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_duration(self.native()) }
+    }
+
+    /// The frame rate in frames per second.
+    pub fn fps(&self) -> f64 {
+        unsafe { sb::C_skottie_Animation_fps(self.native()) }
+    }
+
+    pub fn size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { sb::C_skottie_Animation_size(self.native(), size.native_mut()) };
+        size
+    }
+
+    /// Updates the animation state to the normalized time `t`, where `0` selects the first and
+    /// `1` the final frame.
+    pub fn seek(&mut self, t: scalar) -> &mut Self {
+        unsafe { sb::C_skottie_Animation_seek(self.native_mut(), t) }
+        self
+    }
+
+    /// Updates the animation state to the frame index `frame`, which may be fractional.
+    ///
+    /// Frames are counted from the in point of the animation, so `frame` matches the frame
+    /// numbers of the Lottie file only if its in point is `0`.
+    pub fn seek_frame(&mut self, frame: f64) -> &mut Self {
+        let frames = self.duration() * self.fps();
+        self.seek_normalized(frame, frames)
+    }
+
+    /// Updates the animation state to the time `t` in seconds.
+    ///
+    /// The time is measured from the in point of the animation, so `t` matches the timeline of
+    /// the Lottie file only if its in point is `0`.
+    pub fn seek_frame_time(&mut self, t: f64) -> &mut Self {
+        let duration = self.duration();
+        self.seek_normalized(t, duration)
+    }
+
+    fn seek_normalized(&mut self, t: f64, length: f64) -> &mut Self {
+        let t = if length > 0.0 { t / length } else { 0.0 };
+        self.seek(t as scalar)
+    }
+
+    /// Draws the current animation frame to `canvas`, scaled to fit `dst`.
+    ///
+    /// If `dst` is `None`, the frame is drawn at its intrinsic `size()`.
+    pub fn render<'a>(&self, canvas: &mut Canvas, dst: impl Into<Option<&'a Rect>>) {
+        unsafe {
+            sb::C_skottie_Animation_render(
+                self.native(),
+                canvas.native_mut(),
+                dst.into().native_ptr_or_null(),
+            )
+        }
+    }
+}
+
+/// Configures how animations are built.
+pub type Builder = RefHandle<skottie_Animation_Builder>;
+
+impl NativeDrop for skottie_Animation_Builder {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_Animation_Builder_delete(self) }
+    }
+}
+
+impl Default for RefHandle<skottie_Animation_Builder> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RefHandle<skottie_Animation_Builder> {
+    pub fn new() -> Self {
+        Self::from_ptr(unsafe { sb::C_skottie_Animation_Builder_new() }).unwrap()
+    }
+
+    /// Reports the warnings and errors of the animations this builder makes to `logger`.
+    pub fn set_logger(&mut self, logger: impl Logger) -> &mut Self {
+        let param = new_logger_param(logger);
+        unsafe { sb::C_skottie_Animation_Builder_setLogger(self.native_mut(), &param) }
+        self
+    }
+
+    /// Loads the external resources of the animations this builder makes from `provider`.
+    pub fn set_resource_provider(&mut self, provider: impl ResourceProvider) -> &mut Self {
+        let param = new_resource_provider_param(provider);
+        unsafe { sb::C_skottie_Animation_Builder_setResourceProvider(self.native_mut(), &param) }
+        self
+    }
+
+    /// Resolves the fonts of text layers with `font_mgr` instead of the default font manager.
+    pub fn set_font_manager(&mut self, font_mgr: FontMgr) -> &mut Self {
+        unsafe {
+            sb::C_skottie_Animation_Builder_setFontManager(self.native_mut(), font_mgr.into_ptr())
+        }
+        self
+    }
+
+    /// Passes the properties of the animations this builder makes to `observer`.
+    pub fn set_property_observer(&mut self, observer: impl PropertyObserver) -> &mut Self {
+        let param = new_property_observer_param(observer);
+        unsafe { sb::C_skottie_Animation_Builder_setPropertyObserver(self.native_mut(), &param) }
+        self
+    }
+
+    /// Builds an animation from the Lottie JSON in `bytes`.
+    ///
+    /// Returns `None` if the animation can not be built, the reason is reported to the logger.
+    pub fn make_from_bytes(&mut self, bytes: &[u8]) -> Option<Animation> {
+        Animation::from_ptr(unsafe {
+            sb::C_skottie_Animation_Builder_make(
+                self.native_mut(),
+                bytes.as_ptr() as _,
+                bytes.len(),
+            )
+        })
+    }
+
+    /// Builds an animation from the Lottie JSON in `str`.
+    pub fn make_from_str(&mut self, str: impl AsRef<str>) -> Option<Animation> {
+        self.make_from_bytes(str.as_ref().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Animation, Builder};
+    use crate::skottie::LogLevel;
+    use crate::{Color, Rect, Size, Surface};
+    use std::sync::{Arc, Mutex};
+
+    const SOLID: &str = r##"{
+        "v": "5.5.2", "fr": 30, "ip": 0, "op": 60, "w": 100, "h": 50,
+        "layers": [
+            { "ty": 1, "sc": "#ff0000", "sw": 100, "sh": 50, "ip": 0, "op": 60, "st": 0, "ks": {} }
+        ]
+    }"##;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn animation_properties() {
+        let animation = Animation::from_str(SOLID).unwrap();
+        assert_eq!(animation.version(), "5.5.2");
+        assert_eq!(animation.fps(), 30.0);
+        assert_eq!(animation.duration(), 2.0);
+        assert_eq!(animation.size(), Size::new(100.0, 50.0));
+    }
+
+    #[test]
+    fn seek_and_render_into_dst() {
+        let mut animation = Animation::from_str(SOLID).unwrap();
+        animation.seek_frame(30.0).seek_frame_time(1.5).seek(1.0);
+        let mut surface = Surface::new_raster_n32_premul((40, 40)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        animation.render(surface.canvas(), &Rect::new(0.0, 0.0, 40.0, 20.0));
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((20, 10)), Color::RED);
+        assert_eq!(pixmap.get_color((20, 30)), Color::WHITE);
+    }
+
+    #[test]
+    fn errors_are_logged() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut builder = Builder::new();
+        {
+            let log = log.clone();
+            builder.set_logger(move |level, message: &str, _json: Option<&str>| {
+                log.lock().unwrap().push((level, message.to_string()))
+            });
+        }
+        assert!(builder.make_from_str("{ not json").is_none());
+        drop(builder);
+        let log = log.lock().unwrap();
+        assert!(log.iter().any(|(level, _)| *level == LogLevel::Error));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::prelude::*;
+use skia_bindings as sb;
+use skia_bindings::RustLogger_Param;
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(i32)]
+pub enum LogLevel {
+    Warning = sb::skottie_Logger_Level::kWarning as _,
+    Error = sb::skottie_Logger_Level::kError as _,
+}
+
+impl NativeTransmutable<sb::skottie_Logger_Level> for LogLevel {}
+#[test]
+fn test_log_level_layout() {
+    LogLevel::test_layout()
+}
+
+/// Receives the warnings and errors that are reported while an animation is built.
+///
+/// Closures with a matching signature are loggers, too.
+pub trait Logger: Send + Sync + 'static {
+    /// Logs a `message`, `json` is the part of the animation the message refers to, if
+    /// available.
+    fn log(&self, level: LogLevel, message: &str, json: Option<&str>);
+}
+
+impl<F> Logger for F
+where
+    F: Fn(LogLevel, &str, Option<&str>) + Send + Sync + 'static,
+{
+    fn log(&self, level: LogLevel, message: &str, json: Option<&str>) {
+        self(level, message, json)
+    }
+}
+
+pub(crate) fn new_logger_param<L: Logger>(logger: L) -> RustLogger_Param {
+    RustLogger_Param {
+        data: Box::into_raw(Box::new(logger)) as _,
+        log: Some(logger_log::<L>),
+        drop: Some(logger_drop::<L>),
+    }
+}
+
+extern "C" fn logger_log<L: Logger>(
+    data: *mut c_void,
+    level: sb::skottie_Logger_Level,
+    message: *const c_char,
+    json: *const c_char,
+) {
+    let logger = unsafe { &*(data as *const L) };
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    let json =
+        (!json.is_null()).if_true_then_some(|| unsafe { CStr::from_ptr(json) }.to_string_lossy());
+    logger.log(
+        LogLevel::from_native(level),
+        &message,
+        json.as_ref().map(|json| json.as_ref()),
+    )
+}
+
+extern "C" fn logger_drop<L>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut L) })
+}
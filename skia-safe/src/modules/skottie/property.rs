@@ -0,0 +1,316 @@
+use super::str_or_empty;
+use crate::interop;
+use crate::interop::AsStr;
+use crate::prelude::*;
+use crate::{scalar, Color, Point, Typeface, Vector};
+use skia_bindings as sb;
+use skia_bindings::{RustPropertyObserver_Param, SkTypeface};
+use std::ffi::c_void;
+use std::os::raw::c_char;
+use std::ptr;
+
+#[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[repr(C)]
+pub struct TransformPropertyValue {
+    pub anchor_point: Point,
+    pub position: Point,
+    pub scale: Vector,
+    pub rotation: scalar,
+    pub skew: scalar,
+    pub skew_axis: scalar,
+}
+
+impl NativeTransmutable<sb::skottie_TransformPropertyValue> for TransformPropertyValue {}
+#[test]
+fn test_transform_property_value_layout() {
+    TransformPropertyValue::test_layout()
+}
+
+/// The text of a text layer and the parts of its style that can be changed.
+///
+/// Properties like the alignment and the text box are kept when the value is set.
+#[derive(Clone)]
+pub struct TextPropertyValue {
+    pub typeface: Option<Typeface>,
+    pub text: String,
+    pub text_size: f32,
+    pub fill_color: Color,
+    pub stroke_color: Color,
+}
+
+/// Reads and overrides the color of a node.
+pub struct ColorPropertyHandle(*mut c_void);
+
+impl Drop for ColorPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_ColorPropertyHandle_delete(self.0) }
+    }
+}
+
+impl ColorPropertyHandle {
+    pub fn get(&self) -> Color {
+        Color::from_native(unsafe { sb::C_skottie_ColorPropertyHandle_get(self.0) })
+    }
+
+    pub fn set(&mut self, color: impl Into<Color>) {
+        unsafe { sb::C_skottie_ColorPropertyHandle_set(self.0, color.into().into_native()) }
+    }
+}
+
+/// Reads and overrides the opacity of a node, in the range of `0` to `100`.
+pub struct OpacityPropertyHandle(*mut c_void);
+
+impl Drop for OpacityPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_delete(self.0) }
+    }
+}
+
+impl OpacityPropertyHandle {
+    pub fn get(&self) -> f32 {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_get(self.0) }
+    }
+
+    pub fn set(&mut self, opacity: f32) {
+        unsafe { sb::C_skottie_OpacityPropertyHandle_set(self.0, opacity) }
+    }
+}
+
+/// Reads and overrides the transform of a layer.
+pub struct TransformPropertyHandle(*mut c_void);
+
+impl Drop for TransformPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_TransformPropertyHandle_delete(self.0) }
+    }
+}
+
+impl TransformPropertyHandle {
+    pub fn get(&self) -> TransformPropertyValue {
+        let mut value = TransformPropertyValue::default();
+        unsafe { sb::C_skottie_TransformPropertyHandle_get(self.0, value.native_mut()) };
+        value
+    }
+
+    pub fn set(&mut self, value: &TransformPropertyValue) {
+        unsafe { sb::C_skottie_TransformPropertyHandle_set(self.0, value.native()) }
+    }
+}
+
+/// Reads and overrides the text of a text layer.
+pub struct TextPropertyHandle(*mut c_void);
+
+impl Drop for TextPropertyHandle {
+    fn drop(&mut self) {
+        unsafe { sb::C_skottie_TextPropertyHandle_delete(self.0) }
+    }
+}
+
+impl TextPropertyHandle {
+    pub fn get(&self) -> TextPropertyValue {
+        let mut typeface: *mut SkTypeface = ptr::null_mut();
+        let mut text = interop::String::default();
+        let mut text_size = 0.0;
+        let mut fill_color = Color::default();
+        let mut stroke_color = Color::default();
+        unsafe {
+            sb::C_skottie_TextPropertyHandle_get(
+                self.0,
+                &mut typeface,
+                text.native_mut(),
+                &mut text_size,
+                fill_color.native_mut(),
+                stroke_color.native_mut(),
+            )
+        };
+        TextPropertyValue {
+            typeface: Typeface::from_ptr(typeface),
+            text: text.as_str().into(),
+            text_size,
+            fill_color,
+            stroke_color,
+        }
+    }
+
+    pub fn set(&mut self, value: &TextPropertyValue) {
+        let text = value.text.as_bytes();
+        unsafe {
+            sb::C_skottie_TextPropertyHandle_set(
+                self.0,
+                value.typeface.clone().into_ptr_or_null(),
+                text.as_ptr() as _,
+                text.len(),
+                value.text_size,
+                value.fill_color.into_native(),
+                value.stroke_color.into_native(),
+            )
+        }
+    }
+}
+
+/// Receives the properties of the nodes while an animation is built, so that they can be
+/// overridden later on.
+///
+/// The nodes are identified by the names they have in the animation. The property handles stay
+/// valid after the animation was built, they are ignored by default.
+pub trait PropertyObserver: 'static {
+    fn on_color_property(&mut self, _node_name: &str, _handle: ColorPropertyHandle) {}
+    fn on_opacity_property(&mut self, _node_name: &str, _handle: OpacityPropertyHandle) {}
+    fn on_text_property(&mut self, _node_name: &str, _handle: TextPropertyHandle) {}
+    fn on_transform_property(&mut self, _node_name: &str, _handle: TransformPropertyHandle) {}
+    fn on_enter_node(&mut self, _node_name: &str) {}
+    fn on_leaving_node(&mut self, _node_name: &str) {}
+}
+
+pub(crate) fn new_property_observer_param<O: PropertyObserver>(
+    observer: O,
+) -> RustPropertyObserver_Param {
+    RustPropertyObserver_Param {
+        data: Box::into_raw(Box::new(observer)) as _,
+        onColorProperty: Some(observer_on_color_property::<O>),
+        onOpacityProperty: Some(observer_on_opacity_property::<O>),
+        onTextProperty: Some(observer_on_text_property::<O>),
+        onTransformProperty: Some(observer_on_transform_property::<O>),
+        onEnterNode: Some(observer_on_enter_node::<O>),
+        onLeavingNode: Some(observer_on_leaving_node::<O>),
+        drop: Some(observer_drop::<O>),
+    }
+}
+
+unsafe fn as_observer<'a, O>(data: *mut c_void) -> &'a mut O {
+    &mut *(data as *mut O)
+}
+
+extern "C" fn observer_on_color_property<O: PropertyObserver>(
+    data: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut c_void,
+) {
+    let handle = ColorPropertyHandle(handle);
+    let (observer, node_name) = unsafe { (as_observer::<O>(data), str_or_empty(node_name)) };
+    observer.on_color_property(&node_name, handle)
+}
+
+extern "C" fn observer_on_opacity_property<O: PropertyObserver>(
+    data: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut c_void,
+) {
+    let handle = OpacityPropertyHandle(handle);
+    let (observer, node_name) = unsafe { (as_observer::<O>(data), str_or_empty(node_name)) };
+    observer.on_opacity_property(&node_name, handle)
+}
+
+extern "C" fn observer_on_text_property<O: PropertyObserver>(
+    data: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut c_void,
+) {
+    let handle = TextPropertyHandle(handle);
+    let (observer, node_name) = unsafe { (as_observer::<O>(data), str_or_empty(node_name)) };
+    observer.on_text_property(&node_name, handle)
+}
+
+extern "C" fn observer_on_transform_property<O: PropertyObserver>(
+    data: *mut c_void,
+    node_name: *const c_char,
+    handle: *mut c_void,
+) {
+    let handle = TransformPropertyHandle(handle);
+    let (observer, node_name) = unsafe { (as_observer::<O>(data), str_or_empty(node_name)) };
+    observer.on_transform_property(&node_name, handle)
+}
+
+extern "C" fn observer_on_enter_node<O: PropertyObserver>(
+    data: *mut c_void,
+    node_name: *const c_char,
+) {
+    let (observer, node_name) = unsafe { (as_observer::<O>(data), str_or_empty(node_name)) };
+    observer.on_enter_node(&node_name)
+}
+
+extern "C" fn observer_on_leaving_node<O: PropertyObserver>(
+    data: *mut c_void,
+    node_name: *const c_char,
+) {
+    let (observer, node_name) = unsafe { (as_observer::<O>(data), str_or_empty(node_name)) };
+    observer.on_leaving_node(&node_name)
+}
+
+extern "C" fn observer_drop<O>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut O) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorPropertyHandle, OpacityPropertyHandle, PropertyObserver};
+    use crate::skottie::Builder;
+    use crate::{Color, Surface};
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    const BOX: &str = r#"{
+        "v": "5.5.2", "fr": 30, "ip": 0, "op": 60, "w": 100, "h": 100,
+        "layers": [{
+            "ty": 4, "nm": "box", "ip": 0, "op": 60, "st": 0,
+            "ks": {
+                "o": { "a": 0, "k": 100 }, "r": { "a": 0, "k": 0 },
+                "p": { "a": 0, "k": [0, 0] }, "a": { "a": 0, "k": [0, 0] },
+                "s": { "a": 0, "k": [100, 100] }
+            },
+            "shapes": [
+                { "ty": "rc", "nm": "rect", "p": { "a": 0, "k": [50, 50] },
+                  "s": { "a": 0, "k": [100, 100] }, "r": { "a": 0, "k": 0 } },
+                { "ty": "fl", "nm": "fill", "c": { "a": 0, "k": [1, 0, 0, 1] },
+                  "o": { "a": 0, "k": 100 } }
+            ]
+        }]
+    }"#;
+
+    #[derive(Default)]
+    struct Handles {
+        colors: HashMap<String, ColorPropertyHandle>,
+        opacities: HashMap<String, OpacityPropertyHandle>,
+    }
+
+    #[derive(Default, Clone)]
+    struct Observer(Rc<RefCell<Handles>>);
+
+    impl PropertyObserver for Observer {
+        fn on_color_property(&mut self, node_name: &str, handle: ColorPropertyHandle) {
+            self.0.borrow_mut().colors.insert(node_name.into(), handle);
+        }
+
+        fn on_opacity_property(&mut self, node_name: &str, handle: OpacityPropertyHandle) {
+            self.0
+                .borrow_mut()
+                .opacities
+                .insert(node_name.into(), handle);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn override_color_and_opacity() {
+        let observer = Observer::default();
+        let animation = Builder::new()
+            .set_property_observer(observer.clone())
+            .make_from_str(BOX)
+            .unwrap();
+
+        let mut handles = observer.0.borrow_mut();
+        let fill = handles.colors.get_mut("fill").unwrap();
+        assert_eq!(fill.get(), Color::RED);
+        fill.set(Color::BLUE);
+        assert_eq!(handles.opacities["box"].get(), 100.0);
+
+        let mut surface = Surface::new_raster_n32_premul((100, 100)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        animation.render(surface.canvas(), None);
+        assert_eq!(
+            surface.peek_pixels().unwrap().get_color((50, 50)),
+            Color::BLUE
+        );
+    }
+}
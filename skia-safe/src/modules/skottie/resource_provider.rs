@@ -0,0 +1,157 @@
+use crate::prelude::*;
+use crate::{Data, Image};
+use skia_bindings::{RustResourceProvider_Param, SkData, SkImage};
+use std::borrow::Cow;
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+
+/// Loads the external resources an animation refers to, for example the images of image layers
+/// and the fonts that are not available from the font manager.
+///
+/// All functions return `None` by default, which means that the resource is not available.
+pub trait ResourceProvider: 'static {
+    /// Loads a generic resource that is specified by its path and name.
+    fn load(&self, _resource_path: &str, _resource_name: &str) -> Option<Data> {
+        None
+    }
+
+    /// Loads the image of an image asset.
+    ///
+    /// `resource_id` is the asset's id in the animation.
+    fn load_image(
+        &self,
+        _resource_path: &str,
+        _resource_name: &str,
+        _resource_id: &str,
+    ) -> Option<Image> {
+        None
+    }
+
+    /// Loads the data of a font that is specified by its name and url.
+    fn load_font(&self, _name: &str, _url: &str) -> Option<Data> {
+        None
+    }
+}
+
+pub(crate) fn new_resource_provider_param<P: ResourceProvider>(
+    provider: P,
+) -> RustResourceProvider_Param {
+    RustResourceProvider_Param {
+        data: Box::into_raw(Box::new(provider)) as _,
+        load: Some(resource_provider_load::<P>),
+        loadImage: Some(resource_provider_load_image::<P>),
+        loadFont: Some(resource_provider_load_font::<P>),
+        drop: Some(resource_provider_drop::<P>),
+    }
+}
+
+// Skottie passes nullptr for missing names.
+pub(crate) unsafe fn str_or_empty<'a>(str: *const c_char) -> Cow<'a, str> {
+    if str.is_null() {
+        "".into()
+    } else {
+        CStr::from_ptr(str).to_string_lossy()
+    }
+}
+
+extern "C" fn resource_provider_load<P: ResourceProvider>(
+    data: *mut c_void,
+    resource_path: *const c_char,
+    resource_name: *const c_char,
+) -> *mut SkData {
+    let provider = unsafe { &*(data as *const P) };
+    let (path, name) = unsafe { (str_or_empty(resource_path), str_or_empty(resource_name)) };
+    provider.load(&path, &name).into_ptr_or_null()
+}
+
+extern "C" fn resource_provider_load_image<P: ResourceProvider>(
+    data: *mut c_void,
+    resource_path: *const c_char,
+    resource_name: *const c_char,
+    resource_id: *const c_char,
+) -> *mut SkImage {
+    let provider = unsafe { &*(data as *const P) };
+    let (path, name, id) = unsafe {
+        (
+            str_or_empty(resource_path),
+            str_or_empty(resource_name),
+            str_or_empty(resource_id),
+        )
+    };
+    provider.load_image(&path, &name, &id).into_ptr_or_null()
+}
+
+extern "C" fn resource_provider_load_font<P: ResourceProvider>(
+    data: *mut c_void,
+    name: *const c_char,
+    url: *const c_char,
+) -> *mut SkData {
+    let provider = unsafe { &*(data as *const P) };
+    let (name, url) = unsafe { (str_or_empty(name), str_or_empty(url)) };
+    provider.load_font(&name, &url).into_ptr_or_null()
+}
+
+extern "C" fn resource_provider_drop<P>(data: *mut c_void) {
+    drop(unsafe { Box::from_raw(data as *mut P) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceProvider;
+    use crate::skottie::Builder;
+    use crate::{Color, Image, Surface};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    const IMAGE_LAYER: &str = r#"{
+        "v": "5.5.2", "fr": 30, "ip": 0, "op": 60, "w": 10, "h": 10,
+        "assets": [{ "id": "image_0", "w": 10, "h": 10, "u": "images/", "p": "red.png" }],
+        "layers": [{ "ty": 2, "refId": "image_0", "ip": 0, "op": 60, "st": 0, "ks": {} }]
+    }"#;
+
+    struct Provider(Rc<RefCell<Vec<String>>>);
+
+    impl ResourceProvider for Provider {
+        fn load_image(&self, path: &str, name: &str, id: &str) -> Option<Image> {
+            self.0
+                .borrow_mut()
+                .push(format!("{}{} ({})", path, name, id));
+            let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+            surface.canvas().clear(Color::RED);
+            Some(surface.image_snapshot())
+        }
+    }
+
+    #[test]
+    fn images_are_loaded_from_the_provider() {
+        let loaded = Rc::new(RefCell::new(Vec::new()));
+        let animation = Builder::new()
+            .set_resource_provider(Provider(loaded.clone()))
+            .make_from_str(IMAGE_LAYER)
+            .unwrap();
+        assert_eq!(*loaded.borrow(), vec!["images/red.png (image_0)"]);
+
+        let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        animation.render(surface.canvas(), None);
+        assert_eq!(surface.peek_pixels().unwrap().get_color((5, 5)), Color::RED);
+    }
+
+    #[test]
+    fn missing_images_are_not_drawn() {
+        struct Empty;
+        impl ResourceProvider for Empty {}
+
+        let animation = Builder::new()
+            .set_resource_provider(Empty)
+            .make_from_str(IMAGE_LAYER)
+            .unwrap();
+        let mut surface = Surface::new_raster_n32_premul((10, 10)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        animation.render(surface.canvas(), None);
+        assert_eq!(
+            surface.peek_pixels().unwrap().get_color((5, 5)),
+            Color::WHITE
+        );
+    }
+}
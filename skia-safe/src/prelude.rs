@@ -437,6 +437,14 @@ impl<N: NativeDrop> RefHandle<N> {
     pub(crate) fn from_ptr(ptr: *mut N) -> Option<Self> {
         ptr.into_option().map(Self)
     }
+
+    /// Consumes the RefHandle and returns the pointer to the native object, which is
+    /// not dropped anymore.
+    pub(crate) fn into_ptr(self) -> *mut N {
+        let ptr = self.0;
+        mem::forget(self);
+        ptr
+    }
 }
 
 /// A wrapper type represented by a reference counted pointer
@@ -1,2 +1,5 @@
 pub mod canvas;
 pub use self::canvas::Canvas;
+
+pub mod dom;
+pub use self::dom::Dom;
@@ -1,18 +1,20 @@
-use crate::interop::DynamicMemoryWStream;
+use crate::interop::{DynamicMemoryWStream, WriterStream};
 use crate::prelude::*;
 use crate::{Data, Rect};
 use skia_bindings as sb;
 use skia_bindings::SkCanvas;
+use std::io;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
-use std::ptr;
+use std::{mem, ptr};
 
-pub struct Canvas {
+/// An SVG canvas that writes to memory, or to a writer if `Stream` is a [`WriterStream`].
+pub struct Canvas<Stream = DynamicMemoryWStream> {
     canvas: *mut SkCanvas,
-    stream: Pin<Box<DynamicMemoryWStream>>,
+    stream: Pin<Box<Stream>>,
 }
 
-impl Drop for Canvas {
+impl<S> Drop for Canvas<S> {
     fn drop(&mut self) {
         unsafe {
             sb::C_SkCanvas_delete(self.canvas);
@@ -20,7 +22,7 @@ impl Drop for Canvas {
     }
 }
 
-impl Deref for Canvas {
+impl<S> Deref for Canvas<S> {
     type Target = crate::Canvas;
 
     fn deref(&self) -> &Self::Target {
@@ -28,7 +30,7 @@ impl Deref for Canvas {
     }
 }
 
-impl DerefMut for Canvas {
+impl<S> DerefMut for Canvas<S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         crate::Canvas::borrow_from_native(unsafe { &mut *self.canvas })
     }
@@ -72,6 +74,33 @@ impl Canvas {
     }
 }
 
+impl<W: io::Write> Canvas<WriterStream<W>> {
+    /// Creates a new SVG canvas that writes the SVG to `writer` while it is drawn.
+    pub fn to_writer(bounds: impl AsRef<Rect>, writer: W, flags: impl Into<Option<Flags>>) -> Self {
+        let bounds = bounds.as_ref();
+        let flags = flags.into().unwrap_or_default();
+        let mut stream = Box::pin(WriterStream::new(writer));
+        let canvas =
+            unsafe { sb::C_SkSVGCanvas_Make(bounds.native(), stream.stream_mut(), flags.bits()) };
+        Canvas { canvas, stream }
+    }
+
+    /// Ends the Canvas drawing, flushes the writer and returns it.
+    ///
+    /// Returns the first I/O error the writer returned while the SVG was written.
+    pub fn end(mut self) -> io::Result<W> {
+        // deleting the canvas writes the end of the SVG.
+        unsafe {
+            sb::C_SkCanvas_delete(self.canvas);
+        }
+        self.canvas = ptr::null_mut();
+        // the canvas is deleted already, so it's fine to move out the stream and forget self.
+        let stream = unsafe { ptr::read(&self.stream) };
+        mem::forget(self);
+        Pin::into_inner(stream).into_writer()
+    }
+}
+
 #[test]
 fn test_svg() {
     use crate::Paint;
@@ -93,3 +122,16 @@ fn test_svg_without_ending() {
     let paint = Paint::default();
     canvas.draw_circle((10, 10), 10.0, &paint);
 }
+
+#[test]
+fn test_svg_to_writer() {
+    use crate::Paint;
+
+    let mut canvas = Canvas::to_writer(&Rect::from_size((20, 20)), Vec::new(), None);
+    let paint = Paint::default();
+    canvas.draw_circle((10, 10), 10.0, &paint);
+    let svg = canvas.end().unwrap();
+    let contents = String::from_utf8_lossy(&svg);
+    assert!(contents.contains(r#"<ellipse cx="10" cy="10" rx="10" ry="10"/>"#));
+    assert!(contents.contains(r#"</svg>"#));
+}
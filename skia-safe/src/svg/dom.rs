@@ -0,0 +1,85 @@
+use crate::interop::MemoryStream;
+use crate::prelude::*;
+use crate::{Canvas, Data, Size};
+use skia_bindings as sb;
+use skia_bindings::{SkRefCntBase, SkSVGDOM};
+
+/// A parsed SVG document that can be rendered to a [`Canvas`].
+///
+/// Note that the m79 SVG model does not support `<image>` elements and offers no hook to load
+/// external resources, so the document is rendered without them.
+pub type Dom = RCHandle<SkSVGDOM>;
+
+impl NativeBase<SkRefCntBase> for SkSVGDOM {}
+
+impl NativeRefCountedBase for SkSVGDOM {
+    type Base = SkRefCntBase;
+}
+
+impl RCHandle<SkSVGDOM> {
+    /// Parses the SVG document in `bytes`.
+    ///
+    /// Returns `None` if `bytes` does not contain a valid SVG document.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Dom> {
+        let mut stream = MemoryStream::from_bytes(bytes);
+        Dom::from_ptr(unsafe { sb::C_SkSVGDOM_MakeFromStream(stream.native_mut().as_stream_mut()) })
+    }
+
+    /// Parses the SVG document in `data`.
+    pub fn from_data(data: &Data) -> Option<Dom> {
+        Self::from_bytes(data.as_bytes())
+    }
+
+    /// The size of the viewport the document is rendered into.
+    ///
+    /// Initially, this is the intrinsic size of the document's root element.
+    pub fn container_size(&self) -> Size {
+        let mut size = Size::default();
+        unsafe { sb::C_SkSVGDOM_containerSize(self.native(), size.native_mut()) };
+        size
+    }
+
+    pub fn set_container_size(&mut self, size: impl Into<Size>) -> &mut Self {
+        unsafe { sb::C_SkSVGDOM_setContainerSize(self.native_mut(), size.into().native()) };
+        self
+    }
+
+    /// Renders the document to `canvas`.
+    pub fn render(&self, canvas: &mut Canvas) {
+        unsafe { sb::C_SkSVGDOM_render(self.native(), canvas.native_mut()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dom;
+    use crate::{Color, Size, Surface};
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10">
+        <rect x="10" y="0" width="10" height="10" fill="#ff0000"/>
+    </svg>"##;
+
+    #[test]
+    fn parse_and_query_container_size() {
+        let mut dom = Dom::from_bytes(SVG.as_bytes()).unwrap();
+        assert_eq!(dom.container_size(), Size::new(20.0, 10.0));
+        dom.set_container_size((40, 20));
+        assert_eq!(dom.container_size(), Size::new(40.0, 20.0));
+    }
+
+    #[test]
+    fn invalid_svg_fails_to_parse() {
+        assert!(Dom::from_bytes(b"<svg").is_none());
+    }
+
+    #[test]
+    fn render_to_canvas() {
+        let dom = Dom::from_bytes(SVG.as_bytes()).unwrap();
+        let mut surface = Surface::new_raster_n32_premul((20, 10)).unwrap();
+        surface.canvas().clear(Color::WHITE);
+        dom.render(surface.canvas());
+        let pixmap = surface.peek_pixels().unwrap();
+        assert_eq!(pixmap.get_color((5, 5)), Color::WHITE);
+        assert_eq!(pixmap.get_color((15, 5)), Color::RED);
+    }
+}